@@ -6,4 +6,6 @@ pub enum Command {
     Left,
     Right,
     RotateClockWise,
+    RotateCounterClockWise,
+    Hold,
 }
\ No newline at end of file
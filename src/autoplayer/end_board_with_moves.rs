@@ -1,6 +1,6 @@
 //Imports
-use Board;
-use Command;
+use board::Board;
+use autoplayer::commands::Command;
 
 // This struct is used to store the board computed by the commands in commands.
 #[derive(Debug, Clone)]
@@ -0,0 +1,171 @@
+// Imports
+use board::Board;
+use pieces::piece_type::PieceType;
+use autoplayer::evaluator::{Evaluator, EvaluatorKind};
+
+
+// Make this struct copyable, cloneable, printable and comparable.
+// Represents the heuristic the autoplayer always had.
+//
+// - heights:           the weight of the sum of all column heights
+// - completed_rows:    the weight of the completed rows
+// - holes:             the weight of the empty cells below a block
+// - walls:             the weight of the blocks touching a wall, blocks on the floor count twice
+// - touching:          the weight of the sides of blocks touching another block
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Classic {
+    pub heights: f64,
+    pub completed_rows: f64,
+    pub holes: f64,
+    pub walls: f64,
+    pub touching: f64,
+}
+
+
+impl Default for Classic {
+    fn default() -> Classic {
+        Classic::new()
+    }
+}
+
+
+impl Classic {
+    pub fn new() -> Classic {
+        Classic {
+            heights: -2.5,
+            completed_rows: 8.0,
+            holes: -4.5,
+            walls: 4.2,
+            touching: 4.0,
+        }
+    }
+
+
+    ///
+    /// This function is used to compute the heuristics and evaluate the board given.
+    /// -2.5 * sum of all heights // 8.0 * completed rows // -4.5 * number of holes // 4.2 * touching walls // 4.0 * touching pieces
+    ///
+    #[allow(clippy::needless_range_loop)]
+    pub fn heuristic(&self, board: &Board) -> f64 {
+        let mut sum_of_heights = 0.0;
+        let mut completed_rows = 0.0;
+        let mut number_of_holes = 0.0;
+        let mut wall_touched = 0.0;
+
+        // Compute the sum of heights, the number of holes and wall touched.
+        let mut heights = vec![0.0; board.columns];
+        let mut first_piece_found;
+        for column in 0..board.columns {
+            first_piece_found = false;
+            for row in 0..board.rows {
+                if (column == 0 || column == board.columns - 1) && board.board[row][column] != PieceType::None  {
+                    wall_touched += 1.0;
+                }
+                if row == board.rows - 1 && board.board[row][column] != PieceType::None  {
+                    wall_touched += 2.0;
+                }
+                if board.board[row][column] == PieceType::None {
+                    if first_piece_found {
+                        number_of_holes += 1.0;
+                    }
+                } else {
+                    if !first_piece_found {
+                        heights[column] = board.rows as f64 - row as f64;
+                    }
+                    first_piece_found = true;
+                }
+            }
+        }
+
+        // Get the sum of heights.
+        for height in heights {
+            sum_of_heights += height;
+        }
+
+        let mut row_completed;
+
+        // Compute the number of touching pieces.
+        let mut touching_pieces_score = 0.0;
+        for x in 0..board.rows {
+            row_completed = true;
+            for y in 0..board.columns {
+                if board.board[x][y] != PieceType::None {
+                    if x < (board.rows - 1) && board.board[x + 1][y] != PieceType::None {
+                        touching_pieces_score += 1.0;
+                    }
+                    if x > 0 && board.board[x - 1][y] != PieceType::None {
+                        touching_pieces_score += 1.0;
+                    }
+                    if y < (board.columns - 1) && board.board[x][y + 1] != PieceType::None {
+                        touching_pieces_score += 1.0;
+                    }
+                    if y > 0 && board.board[x][y - 1] != PieceType::None {
+                        touching_pieces_score += 1.0;
+                    }
+                } else {
+                    row_completed = false;
+                }
+            }
+            if row_completed {
+                completed_rows += 1.0;
+            }
+        }
+
+
+
+        sum_of_heights * self.heights + completed_rows * self.completed_rows + number_of_holes * self.holes + wall_touched * self.walls + touching_pieces_score * self.touching
+    }
+
+
+    ///
+    /// This function returns the weights by their name, in the order of the fields.
+    ///
+    fn weights_mut(&mut self) -> [(&'static str, &mut f64); 5] {
+        [
+            ("heights", &mut self.heights),
+            ("completed_rows", &mut self.completed_rows),
+            ("holes", &mut self.holes),
+            ("walls", &mut self.walls),
+            ("touching", &mut self.touching),
+        ]
+    }
+}
+
+
+impl Evaluator for Classic {
+    ///
+    /// The heuristic judges whole boards, a placement is worth the change of the heuristic.
+    /// The heuristic of the boards after completed rows were deleted isn't comparable, the touching pieces of the rows are gone.
+    ///
+    fn evaluate(&self, board: &Board, end_board: &Board) -> f64 {
+        self.heuristic(end_board) - self.heuristic(board)
+    }
+
+
+    fn kind(&self) -> EvaluatorKind {
+        EvaluatorKind::Classic
+    }
+
+
+    fn weights(&self) -> Vec<(&'static str, f64)> {
+        let mut classic = *self;
+        let weights = classic.weights_mut();
+        weights.iter().map(|(name, weight)| (*name, **weight)).collect()
+    }
+
+
+    fn set_weight(&mut self, name: &str, weight: f64) -> bool {
+        match self.weights_mut().iter_mut().find(|(weight_name, _)| *weight_name == name) {
+            Some((_, value)) => {
+                **value = weight;
+                true
+            }
+            None => false,
+        }
+    }
+
+
+    fn box_clone(&self) -> Box<dyn Evaluator> {
+        Box::new(*self)
+    }
+}
@@ -0,0 +1,241 @@
+// Imports
+use board::Board;
+use pieces::piece_type::PieceType;
+
+
+// The amount of features.
+pub const FEATURES: usize = 9;
+
+
+// Make this struct copyable, cloneable, printable and comparable.
+// Represents the standard features of a placement. All but the first three are measured after the completed rows were deleted.
+// Heights are counted in cells from the floor.
+//
+// - landing_height:        the height of the middle of the placed piece
+// - eroded_piece_cells:    the completed rows times the cells of the placed piece in them
+// - completed_rows:        the amount of rows the placement completes
+// - aggregate_height:      the sum of the column heights
+// - bumpiness:             the sum of the height differences of neighbouring columns
+// - holes:                 the empty cells below a block of their column
+// - wells:                 the sum over all wells of 1 + 2 + ... + depth, a well cell is an empty cell with blocks or walls on both sides
+// - row_transitions:       the changes between empty and filled cells along the rows, the walls count as filled
+// - column_transitions:    the changes between empty and filled cells along the columns, the floor counts as filled
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Features {
+    pub landing_height: f64,
+    pub eroded_piece_cells: f64,
+    pub completed_rows: f64,
+    pub aggregate_height: f64,
+    pub bumpiness: f64,
+    pub holes: f64,
+    pub wells: f64,
+    pub row_transitions: f64,
+    pub column_transitions: f64,
+}
+
+
+impl Features {
+    ///
+    /// This function computes the features of placing a piece onto "board", which results in "end_board".
+    ///
+    /// The cells filled in "end_board" but not in "board" are the cells of the placed piece.
+    ///
+    pub fn new(board: &Board, end_board: &Board) -> Features {
+        let rows = end_board.rows;
+        let columns = end_board.columns;
+        let filled = |row: &Vec<PieceType>, column: usize| row[column] != PieceType::None;
+
+        // Find the completed rows and the rows of the placed piece.
+        let mut completed = vec![false; rows];
+        let mut completed_rows = 0;
+        let mut eroded_cells = 0;
+        let mut piece_rows: Option<(usize, usize)> = None;
+        for (row, is_completed) in completed.iter_mut().enumerate() {
+            *is_completed = (0..columns).all(|column| filled(&end_board.board[row], column));
+            let piece_cells = (0..columns).filter(|&column| filled(&end_board.board[row], column) && !filled(&board.board[row], column)).count();
+            if piece_cells > 0 {
+                piece_rows = Some(match piece_rows {
+                    Some((top, _)) => (top, row),
+                    None => (row, row),
+                });
+            }
+            if *is_completed {
+                completed_rows += 1;
+                eroded_cells += piece_cells;
+            }
+        }
+
+        // The height of the column below the piece plus half of the piece's height.
+        let landing_height = match piece_rows {
+            Some((top, bottom)) => (rows - 1 - bottom) as f64 + (bottom - top + 1) as f64 / 2.0,
+            None => 0.0,
+        };
+
+        // The board after the completed rows were deleted, the empty rows added on top don't change any feature but the row transitions.
+        let remaining: Vec<&Vec<PieceType>> = (0..rows).filter(|&row| !completed[row]).map(|row| &end_board.board[row]).collect();
+
+        let mut row_transitions = 2 * completed_rows;
+        for row in remaining.iter() {
+            let mut last_filled = true;
+            for column in 0..columns {
+                if filled(row, column) != last_filled {
+                    row_transitions += 1;
+                }
+                last_filled = filled(row, column);
+            }
+            if !last_filled {
+                row_transitions += 1;
+            }
+        }
+
+        let mut heights = vec![0; columns];
+        let mut holes = 0;
+        let mut wells = 0;
+        let mut column_transitions = 0;
+        for (column, height) in heights.iter_mut().enumerate() {
+            // Above the board counts as empty.
+            let mut last_filled = false;
+            let mut well_depth = 0;
+            for (index, row) in remaining.iter().enumerate() {
+                let is_filled = filled(row, column);
+                if is_filled {
+                    if *height == 0 {
+                        *height = remaining.len() - index;
+                    }
+                } else if *height > 0 {
+                    holes += 1;
+                }
+                if is_filled != last_filled {
+                    column_transitions += 1;
+                }
+                last_filled = is_filled;
+
+                let left_filled = column == 0 || filled(row, column - 1);
+                let right_filled = column == columns - 1 || filled(row, column + 1);
+                if !is_filled && left_filled && right_filled {
+                    well_depth += 1;
+                    wells += well_depth;
+                } else {
+                    well_depth = 0;
+                }
+            }
+            if !last_filled {
+                column_transitions += 1;
+            }
+        }
+
+        let bumpiness: usize = heights.windows(2).map(|pair| (pair[0] as isize - pair[1] as isize).unsigned_abs()).sum();
+
+        Features {
+            landing_height,
+            eroded_piece_cells: (completed_rows * eroded_cells) as f64,
+            completed_rows: completed_rows as f64,
+            aggregate_height: heights.iter().sum::<usize>() as f64,
+            bumpiness: bumpiness as f64,
+            holes: holes as f64,
+            wells: wells as f64,
+            row_transitions: row_transitions as f64,
+            column_transitions: column_transitions as f64,
+        }
+    }
+
+
+    ///
+    /// This function returns the features by their name, in the order of the fields.
+    ///
+    pub fn named_mut(&mut self) -> [(&'static str, &mut f64); FEATURES] {
+        [
+            ("landing_height", &mut self.landing_height),
+            ("eroded_piece_cells", &mut self.eroded_piece_cells),
+            ("completed_rows", &mut self.completed_rows),
+            ("aggregate_height", &mut self.aggregate_height),
+            ("bumpiness", &mut self.bumpiness),
+            ("holes", &mut self.holes),
+            ("wells", &mut self.wells),
+            ("row_transitions", &mut self.row_transitions),
+            ("column_transitions", &mut self.column_transitions),
+        ]
+    }
+
+
+    ///
+    /// This function returns the sum of the features multiplied with the given weights.
+    ///
+    pub fn weighted_sum(&self, weights: &Features) -> f64 {
+        self.landing_height * weights.landing_height
+            + self.eroded_piece_cells * weights.eroded_piece_cells
+            + self.completed_rows * weights.completed_rows
+            + self.aggregate_height * weights.aggregate_height
+            + self.bumpiness * weights.bumpiness
+            + self.holes * weights.holes
+            + self.wells * weights.wells
+            + self.row_transitions * weights.row_transitions
+            + self.column_transitions * weights.column_transitions
+    }
+}
+
+
+///
+/// TESTS BEGIN HERE
+///
+#[cfg(test)]
+mod tests {
+    use board::Board;
+    use pieces::piece_type::PieceType;
+    use autoplayer::evaluator::features::Features;
+
+    // Fill the given cells of a copy of the board, with (row, column) counted from the top left.
+    fn fill(board: &Board, cells: &[(usize, usize)]) -> Board {
+        let mut board = board.clone();
+        for &(row, column) in cells {
+            board.board[row][column] = PieceType::Garbage;
+        }
+        board
+    }
+
+    #[test]
+    fn features_of_a_placement() {
+        // A 4 x 4 board, the bottom row is missing its last cell and the second column of the row above has a hole.
+        //      . . . .
+        //      . . . .
+        //      X . X .
+        //      X X X .
+        let board = fill(&Board::new(4, 4), &[(2, 0), (2, 2), (3, 0), (3, 1), (3, 2)]);
+
+        // A vertical I piece in the last column completes the bottom row.
+        let end_board = fill(&board, &[(0, 3), (1, 3), (2, 3), (3, 3)]);
+        let features = Features::new(&board, &end_board);
+
+        assert_eq!(features.landing_height, 2.0);
+        assert_eq!(features.completed_rows, 1.0);
+        assert_eq!(features.eroded_piece_cells, 1.0);
+
+        // After the bottom row is deleted:
+        //      . . . .
+        //      . . . X
+        //      . . . X
+        //      X . X X
+        assert_eq!(features.aggregate_height, 1.0 + 0.0 + 1.0 + 3.0);
+        assert_eq!(features.bumpiness, 1.0 + 1.0 + 2.0);
+        assert_eq!(features.holes, 0.0);
+        assert_eq!(features.wells, 1.0);
+        assert_eq!(features.row_transitions, 2.0 + 2.0 + 2.0 + 2.0);
+        assert_eq!(features.column_transitions, 1.0 + 1.0 + 1.0 + 1.0);
+    }
+
+    #[test]
+    fn holes_and_wells() {
+        //      X . X .
+        //      . . X .
+        //      X . X X
+        let board = fill(&Board::new(3, 4), &[(0, 0), (0, 2), (2, 0), (2, 2), (2, 3)]);
+        let end_board = fill(&board, &[(1, 2)]);
+        let features = Features::new(&board, &end_board);
+
+        assert_eq!(features.landing_height, 1.5);
+        assert_eq!(features.holes, 1.0);
+        // The second column has two wells of depth 1, split by the hole next to them, the last column has one of depth 2.
+        assert_eq!(features.wells, 1.0 + 1.0 + 3.0);
+        assert_eq!(features.aggregate_height, 3.0 + 0.0 + 3.0 + 1.0);
+    }
+}
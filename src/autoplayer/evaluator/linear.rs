@@ -0,0 +1,123 @@
+// Imports
+use board::Board;
+use autoplayer::evaluator::{Evaluator, EvaluatorKind};
+use autoplayer::evaluator::features::Features;
+
+
+// Make this struct copyable, cloneable, printable and comparable.
+// Represents an evaluator that weighs the standard features of a placement.
+//
+// - kind:      the preset the weights started from
+// - weights:   the weight of every feature
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Linear {
+    pub kind: EvaluatorKind,
+    pub weights: Features,
+}
+
+
+impl Default for Linear {
+    fn default() -> Linear {
+        Linear::new()
+    }
+}
+
+
+impl Linear {
+    ///
+    /// This function creates the evaluator with the weights Yiyuan Lee found for aggregate height, completed rows, holes and bumpiness.
+    ///
+    pub fn new() -> Linear {
+        Linear {
+            kind: EvaluatorKind::Linear,
+            weights: Features {
+                landing_height: 0.0,
+                eroded_piece_cells: 0.0,
+                completed_rows: 0.760666,
+                aggregate_height: -0.510066,
+                bumpiness: -0.184483,
+                holes: -0.35663,
+                wells: 0.0,
+                row_transitions: 0.0,
+                column_transitions: 0.0,
+            },
+        }
+    }
+
+
+    ///
+    /// This function creates the evaluator with Pierre Dellacherie's hand tuned weights.
+    ///
+    pub fn dellacherie() -> Linear {
+        Linear {
+            kind: EvaluatorKind::Dellacherie,
+            weights: Features {
+                landing_height: -1.0,
+                eroded_piece_cells: 1.0,
+                completed_rows: 0.0,
+                aggregate_height: 0.0,
+                bumpiness: 0.0,
+                holes: -4.0,
+                wells: -1.0,
+                row_transitions: -1.0,
+                column_transitions: -1.0,
+            },
+        }
+    }
+
+
+    ///
+    /// This function creates the evaluator with the weights of El-Tetris, found with particle swarm optimization.
+    ///
+    pub fn el_tetris() -> Linear {
+        Linear {
+            kind: EvaluatorKind::ElTetris,
+            weights: Features {
+                landing_height: -4.500158825082766,
+                eroded_piece_cells: 3.4181268101392694,
+                completed_rows: 0.0,
+                aggregate_height: 0.0,
+                bumpiness: 0.0,
+                holes: -7.899265427351652,
+                wells: -3.3855972247263626,
+                row_transitions: -3.2178882868487753,
+                column_transitions: -9.348695305445199,
+            },
+        }
+    }
+}
+
+
+impl Evaluator for Linear {
+    fn evaluate(&self, board: &Board, end_board: &Board) -> f64 {
+        Features::new(board, end_board).weighted_sum(&self.weights)
+    }
+
+
+    fn kind(&self) -> EvaluatorKind {
+        self.kind
+    }
+
+
+    fn weights(&self) -> Vec<(&'static str, f64)> {
+        let mut weights = self.weights;
+        let named = weights.named_mut();
+        named.iter().map(|(name, weight)| (*name, **weight)).collect()
+    }
+
+
+    fn set_weight(&mut self, name: &str, weight: f64) -> bool {
+        match self.weights.named_mut().iter_mut().find(|(weight_name, _)| *weight_name == name) {
+            Some((_, value)) => {
+                **value = weight;
+                true
+            }
+            None => false,
+        }
+    }
+
+
+    fn box_clone(&self) -> Box<dyn Evaluator> {
+        Box::new(*self)
+    }
+}
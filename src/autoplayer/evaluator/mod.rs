@@ -0,0 +1,239 @@
+///
+/// This module contains the evaluation functions the autoplayer chooses its placements with, and the weights files they are loaded from.
+///
+pub mod classic;
+pub mod features;
+pub mod linear;
+
+// Imports
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use toml;
+
+use board::Board;
+use self::classic::Classic;
+use self::linear::Linear;
+
+
+///
+/// This trait represents an evaluation function of the autoplayer.
+///
+/// It has to be printable and sendable, so that autoplayers can be printed and play on other threads.
+///
+pub trait Evaluator: Debug + Send {
+    ///
+    /// This function returns the value of placing a piece onto "board", which results in "end_board".
+    ///
+    /// "board" doesn't contain the piece, "end_board" contains it and its completed rows aren't deleted yet.
+    /// Higher values are better.
+    ///
+    fn evaluate(&self, board: &Board, end_board: &Board) -> f64;
+
+    ///
+    /// This function returns which kind of evaluator this is, it's stored in the weights files.
+    ///
+    fn kind(&self) -> EvaluatorKind;
+
+    ///
+    /// This function returns the names and values of all weights, always in the same order.
+    ///
+    fn weights(&self) -> Vec<(&'static str, f64)>;
+
+    ///
+    /// This function sets the weight with the given name. Returns {false} if there is no such weight.
+    ///
+    fn set_weight(&mut self, name: &str, weight: f64) -> bool;
+
+    ///
+    /// This function returns a boxed copy of the evaluator, boxed evaluators can't be cloned otherwise.
+    ///
+    fn box_clone(&self) -> Box<dyn Evaluator>;
+}
+
+
+impl Clone for Box<dyn Evaluator> {
+    fn clone(&self) -> Box<dyn Evaluator> {
+        self.box_clone()
+    }
+}
+
+
+// Make this enum copyable, cloneable, printable, comparable and serializable.
+// Represents the evaluators the autoplayer can use, with their preset weights.
+//
+// - Classic:       the heuristic the autoplayer always had, heights, completed rows, holes, touched walls and touching pieces
+// - Dellacherie:   Pierre Dellacherie's features and weights, landing height, eroded piece cells, row and column transitions, holes and wells
+// - ElTetris:      Dellacherie's features with the weights of El-Tetris
+// - Linear:        aggregate height, completed rows, holes and bumpiness with the weights of Yiyuan Lee, wells and transitions start at 0
+// Dellacherie, ElTetris and Linear weigh the same features, they only start with different weights.
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum EvaluatorKind {
+    Classic,
+    Dellacherie,
+    ElTetris,
+    Linear,
+}
+
+
+impl EvaluatorKind {
+    pub const ALL: [EvaluatorKind; 4] = [EvaluatorKind::Classic, EvaluatorKind::Dellacherie, EvaluatorKind::ElTetris, EvaluatorKind::Linear];
+
+
+    ///
+    /// This function returns the name of the evaluator, as it's written in the weights files and on the command line.
+    ///
+    pub fn name(&self) -> &'static str {
+        match self {
+            EvaluatorKind::Classic => "classic",
+            EvaluatorKind::Dellacherie => "dellacherie",
+            EvaluatorKind::ElTetris => "el-tetris",
+            EvaluatorKind::Linear => "linear",
+        }
+    }
+
+
+    ///
+    /// This function returns the evaluator with the given name, if there is one.
+    ///
+    pub fn from_name(name: &str) -> Option<EvaluatorKind> {
+        EvaluatorKind::ALL.iter().cloned().find(|kind| kind.name() == name)
+    }
+
+
+    ///
+    /// This function creates the evaluator with its preset weights.
+    ///
+    pub fn evaluator(&self) -> Box<dyn Evaluator> {
+        match self {
+            EvaluatorKind::Classic => Box::new(Classic::new()),
+            EvaluatorKind::Dellacherie => Box::new(Linear::dellacherie()),
+            EvaluatorKind::ElTetris => Box::new(Linear::el_tetris()),
+            EvaluatorKind::Linear => Box::new(Linear::new()),
+        }
+    }
+}
+
+
+///
+/// This struct represents a weights file, a TOML file with the evaluator and its weights.
+///
+/// Weights missing in the file keep the preset value of the evaluator.
+///
+/// - evaluator: the kind of the evaluator
+/// - weights:   the weights by their name
+///
+// Make this struct cloneable, printable, comparable and serializable.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WeightsFile {
+    pub evaluator: EvaluatorKind,
+    #[serde(default)]
+    pub weights: BTreeMap<String, f64>,
+}
+
+
+impl WeightsFile {
+    ///
+    /// This function creates the weights file of the given evaluator, containing all of its weights.
+    ///
+    pub fn new(evaluator: &dyn Evaluator) -> WeightsFile {
+        WeightsFile {
+            evaluator: evaluator.kind(),
+            weights: evaluator.weights().into_iter().map(|(name, weight)| (name.to_string(), weight)).collect(),
+        }
+    }
+
+
+    ///
+    /// This function creates the evaluator of the file. Fails if the file contains a weight the evaluator doesn't have.
+    ///
+    pub fn evaluator(&self) -> io::Result<Box<dyn Evaluator>> {
+        let mut evaluator = self.evaluator.evaluator();
+        for (name, weight) in self.weights.iter() {
+            if !evaluator.set_weight(name, *weight) {
+                let error = format!("unknown weight \"{}\" for the {} evaluator", name, self.evaluator.name());
+                return Err(io::Error::new(io::ErrorKind::InvalidData, error));
+            }
+        }
+
+        Ok(evaluator)
+    }
+
+
+    ///
+    /// This function saves the weights file to the given path, missing directories are created.
+    ///
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(directory) = path.parent() {
+            fs::create_dir_all(directory)?;
+        }
+        let text = toml::to_string_pretty(self).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        fs::write(path, text)
+    }
+
+
+    ///
+    /// This function loads the weights file from the given path. Fails if there is no such file or if it is no valid TOML.
+    ///
+    pub fn load(path: &Path) -> io::Result<WeightsFile> {
+        let text = fs::read_to_string(path)?;
+        toml::from_str(&text).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+}
+
+
+///
+/// This function loads the evaluator of the weights file at the given path.
+///
+pub fn load_evaluator(path: &Path) -> io::Result<Box<dyn Evaluator>> {
+    WeightsFile::load(path)?.evaluator()
+}
+
+
+///
+/// TESTS BEGIN HERE
+///
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::fs;
+
+    use autoplayer::evaluator::{load_evaluator, EvaluatorKind, WeightsFile};
+
+    #[test]
+    fn names_of_the_evaluators() {
+        for kind in EvaluatorKind::ALL.iter() {
+            assert_eq!(EvaluatorKind::from_name(kind.name()), Some(*kind));
+            assert_eq!(kind.evaluator().kind(), *kind);
+        }
+        assert_eq!(EvaluatorKind::from_name("el-tetris"), Some(EvaluatorKind::ElTetris));
+        assert_eq!(EvaluatorKind::from_name("unknown"), None);
+    }
+
+    #[test]
+    fn save_and_load_weights() {
+        let path = env::temp_dir().join("tetris_weights_test").join("weights.toml");
+
+        let mut evaluator = EvaluatorKind::ElTetris.evaluator();
+        assert!(evaluator.set_weight("holes", -1.5));
+        assert!(!evaluator.set_weight("unknown", 1.0));
+        WeightsFile::new(&*evaluator).save(&path).unwrap();
+
+        let loaded = load_evaluator(&path).unwrap();
+        assert_eq!(loaded.kind(), EvaluatorKind::ElTetris);
+        assert_eq!(loaded.weights(), evaluator.weights());
+
+        // Missing weights keep their preset, unknown weights are an error.
+        fs::write(&path, "evaluator = \"classic\"\n\n[weights]\nholes = -1.0\n").unwrap();
+        let classic = load_evaluator(&path).unwrap();
+        assert!(classic.weights().contains(&("holes", -1.0)));
+        assert!(classic.weights().contains(&("heights", -2.5)));
+
+        fs::write(&path, "evaluator = \"classic\"\n\n[weights]\nwells = -1.0\n").unwrap();
+        assert!(load_evaluator(&path).is_err());
+    }
+}
@@ -1,27 +1,71 @@
 pub mod commands;
+pub mod evaluator;
 mod end_board_with_moves;
+mod piece_state;
 
 // Imports.
-use game::Game;
+use std::cmp::Ordering;
+use std::collections::HashSet;
+
+use game::{Game, MAX_PREVIEW_SIZE};
 use self::commands::Command;
 use board::Board;
 use game::move_direction::MoveDirection;
 use pieces::piece_type::PieceType;
 use self::end_board_with_moves::EndBoardWithMoves;
+use self::piece_state::PieceState;
+use self::evaluator::Evaluator;
+use self::evaluator::classic::Classic;
+
+
+// The amount of preview pieces the autoplayer looks ahead by default, just the next piece.
+pub const DEFAULT_LOOKAHEAD: usize = 1;
+
+// The amount of placements that are followed further for every piece after the next one.
+const BEAM_WIDTH: usize = 8;
+
+// The commands the search moves the actual piece with, a Down drops the piece as far as it falls.
+// Down comes last, so that of the shortest commands the ones that shift and rotate the piece before it drops are found first.
+const SEARCH_COMMANDS: [Command; 5] = [Command::Left, Command::Right, Command::RotateClockWise, Command::RotateCounterClockWise, Command::Down];
 
 
 // Struct representing the Autoplayer.
 // Make it printable and cloneable.
+//
+// - commands:      the commands for the actual piece, performed from the back
+// - lookahead:     the amount of preview pieces taken into account, 0 only looks at the actual piece
+// - evaluator:     the evaluation function the placements are chosen with
 #[derive(Debug, Clone)]
 pub struct Autoplayer {
     pub commands: Vec<Command>,
+    pub lookahead: usize,
+    pub evaluator: Box<dyn Evaluator>,
+}
+
+
+impl Default for Autoplayer {
+    fn default() -> Autoplayer {
+        Autoplayer::new()
+    }
 }
 
 
 impl Autoplayer {
     pub fn new() -> Autoplayer {
+        Autoplayer::with_lookahead(DEFAULT_LOOKAHEAD)
+    }
+
+
+    pub fn with_lookahead(lookahead: usize) -> Autoplayer {
+        Autoplayer::with_evaluator(Box::new(Classic::new()), lookahead)
+    }
+
+
+    pub fn with_evaluator(evaluator: Box<dyn Evaluator>, lookahead: usize) -> Autoplayer {
         Autoplayer {
             commands: vec![],
+            lookahead,
+            evaluator,
         }
     }
 
@@ -30,7 +74,7 @@ impl Autoplayer {
     /// This function just returns the next command or Command::Down if there is no commands to perform.
     ///
     pub fn perform_move(&mut self, _game: &mut Game) -> Command {
-        if self.commands.len() != 0 {
+        if !self.commands.is_empty() {
             self.commands.pop().unwrap()
         } else {
             Command::Down
@@ -38,17 +82,66 @@ impl Autoplayer {
     }
 
 
+    ///
+    /// This function performs the next command on the given game.
+    ///
+    /// Whenever a new piece was added, the commands for it get computed right away.
+    /// Returns the command that was performed.
+    ///
+    /// Gravity can move the piece further down than the commands expect. Downs are skipped once the piece can't move down anymore,
+    /// so that only the Down after the last command locks the piece.
+    ///
+    pub fn play_move(&mut self, game: &mut Game) -> Command {
+        while self.commands.last() == Some(&Command::Down) && !game.can_move(MoveDirection::Down) {
+            self.commands.pop();
+        }
+
+        let command = self.perform_move(game);
+        match command {
+            Command::Down => {
+                if game.step(MoveDirection::Down) { self.compute_move(game); }
+            }
+            Command::Left => {
+                if game.step(MoveDirection::Left) { self.compute_move(game); }
+            }
+            Command::Right => {
+                if game.step(MoveDirection::Right) { self.compute_move(game); }
+            }
+            Command::RotateClockWise => {
+                game.rotate_piece_clockwise();
+            }
+            Command::RotateCounterClockWise => {
+                game.rotate_piece_counter_clockwise();
+            }
+            Command::Hold => {
+                game.hold();
+            }
+        }
+
+        command
+    }
+
+
     ///
     /// This function is used to compute the next commands.
     ///
+    /// Every placement of the actual piece is combined with the placements of up to "lookahead" preview pieces,
+    /// the commands of the first placement of the best sequence are performed.
+    /// Commands left over from the last piece are dropped, e.g. if gravity locked it early.
+    ///
     pub fn compute_move(&mut self, game: &mut Game) {
         let all_boards = self.get_all_boards(game);
+        let known_pieces = game.preview(MAX_PREVIEW_SIZE).len();
+
+        // All sequences are compared with the same amount of pieces, so the search can't be deeper than the preview.
+        let lookahead = self.lookahead.min(known_pieces);
+        let board = self.lift(game);
 
-        // Determine the board and commands with the maximum heuristic value.
-        let mut max = std::f64::MIN;
+        // Determine the board and commands with the maximum value.
+        let mut max = f64::MIN;
         let mut best_moves = vec![];
         for x in all_boards.into_iter() {
-            let sc = self.heuristic(&mut x.board.clone());
+            let sc = self.evaluate(game, &board, &x, known_pieces, lookahead);
 
             if sc > max {
                 max = sc;
@@ -56,80 +149,107 @@ impl Autoplayer {
             }
         }
 
-        // Append these commands now.
-        self.commands.append(best_moves.as_mut());
+        // Replace the commands now.
+        self.commands = best_moves;
     }
 
 
     ///
-    /// This function computes all possible boards and the commands needed to compute them.
+    /// This function returns the value of the best placement sequence, that starts with the given placement of the "actual_piece".
     ///
-    fn get_all_boards(&self, game: &Game) -> Vec<EndBoardWithMoves> {
+    /// The value of every placement of the sequence is added up, "board" is the board the actual piece is placed on.
+    ///
+    /// "known_pieces" is the amount of preview pieces that can be seen, pieces beyond the preview are never looked at.
+    /// A hold with nothing held uses up one of them, the next piece is placed instead of the actual one.
+    /// Sequences that would need pieces beyond the preview to place "lookahead" more pieces get the minimum value,
+    /// so only sequences of the same length are compared.
+    /// Only the BEAM_WIDTH placements with the best value are followed further for every piece after the next one.
+    ///
+    fn evaluate(&self, game: &Game, board: &Board, end_board: &EndBoardWithMoves, known_pieces: usize, lookahead: usize) -> f64 {
+        let holds_next_piece = end_board.commands.contains(&Command::Hold) && game.held_piece.piece_type == PieceType::None;
+        let used_pieces = if holds_next_piece { 1 } else { 0 };
+        if known_pieces < used_pieces + lookahead {
+            return f64::MIN;
+        }
 
-        // Do the computations on a game_copy.
-        let mut game_copy = game.clone();
-        let mut all_boards: Vec<EndBoardWithMoves> = vec![];
+        let value = self.evaluator.evaluate(board, &end_board.board);
+        if lookahead == 0 {
+            return value;
+        }
 
-        // The spawn position is at game.board.columns / 2.
-        let mut piece_location = (game.board.columns / 2) + 1;
+        let placed = self.place(game, &end_board.commands);
+        if placed.is_game_over() {
+            return f64::MIN;
+        }
+        let placed_board = self.lift(&placed);
 
-        // The commands needed for a computed board.
-        let mut commands: Vec<Command> = vec![];
+        let mut next_boards: Vec<(f64, EndBoardWithMoves)> = self.get_all_boards(&placed).into_iter()
+            .map(|next_board| (self.evaluator.evaluate(&placed_board, &next_board.board), next_board))
+            .collect();
+        next_boards.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(Ordering::Equal));
 
-        // First compute the board that results by executing no command at all, and push the result into all_boards.
-        all_boards.push(EndBoardWithMoves::new(self.drop(&game_copy), commands.clone()));
+        // The placements of the next piece are all evaluated, pieces further ahead only follow the most promising ones.
+        if lookahead > 1 {
+            next_boards.truncate(BEAM_WIDTH);
+        }
 
-        // Now try all rotations and go left each iteration as long as it is possible to perform left move commands.
-        while 0 < piece_location {
-            for i in 0..4 {
-                if game_copy.rotate_piece_clockwise() && i < 3 {
-                    commands.push(Command::RotateClockWise);
-                    all_boards.push(EndBoardWithMoves::new(self.drop(&game_copy), commands.clone()));
-                }
-            }
-            // This function is used to remove every command other than Command::Left ones.
-            commands.retain(|x| *x == Command::Left);
+        // The piece after this placement is known, it's the next one in the preview.
+        let best = next_boards.iter()
+            .map(|(_, next_board)| self.evaluate(&placed, &placed_board, next_board, known_pieces - used_pieces - 1, lookahead - 1))
+            .fold(f64::MIN, f64::max);
 
-            // If we can move left once more push the command into commands.
-            if game_copy.move_in_direction(MoveDirection::Left) {
-                commands.push(Command::Left);
-                all_boards.push(EndBoardWithMoves::new(self.drop(&game_copy), commands.clone()));
-            }
+        if best == f64::MIN { best } else { value + best }
+    }
 
-            piece_location -= 1;
-        }
 
-        // Now clear all commands and try the right moves.
-        commands.clear();
+    ///
+    /// This function returns a copy of the game after the commands were performed and the piece was dropped.
+    ///
+    /// The commands are performed from the back, like play_move() does it.
+    ///
+    fn place(&self, game: &Game, commands: &[Command]) -> Game {
+        let mut game = game.clone();
+        for command in commands.iter().rev() {
+            self.apply(&mut game, command);
+        }
+        game.hard_drop();
 
-        // Clone to reset the game.
-        game_copy = game.clone();
+        game
+    }
 
-        // Do the same as before but going to the right.
-        game_copy.move_in_direction(MoveDirection::Right);
-        commands.push(Command::Right);
-        all_boards.push(EndBoardWithMoves::new(self.drop(&game_copy), commands.clone()));
-        piece_location = (game.board.columns / 2) + 1;
 
-        // Rotate it again and try all boards. One less iteration because the board starts with coordinate 0.
-        while 1 < piece_location {
-            for i in 0..4 {
-                if game_copy.rotate_piece_clockwise() && i < 3 {
-                    commands.push(Command::RotateClockWise);
-                    all_boards.push(EndBoardWithMoves::new(self.drop(&game_copy), commands.clone()));
-                }
-            }
+    ///
+    /// This function performs the command on the given game, without locking the piece. Returns {true} if the command was successful.
+    ///
+    fn apply(&self, game: &mut Game, command: &Command) -> bool {
+        match command {
+            Command::Down => game.move_in_direction(MoveDirection::Down),
+            Command::Left => game.move_in_direction(MoveDirection::Left),
+            Command::Right => game.move_in_direction(MoveDirection::Right),
+            Command::RotateClockWise => game.rotate_piece_clockwise(),
+            Command::RotateCounterClockWise => game.rotate_piece_counter_clockwise(),
+            Command::Hold => game.hold(),
+        }
+    }
 
-            // Get only the Command::Right commands, remove everything else.
-            commands.retain(|x| *x == Command::Right);
 
-            if game_copy.move_in_direction(MoveDirection::Right) {
-                commands.push(Command::Right);
-                all_boards.push(EndBoardWithMoves::new(self.drop(&game_copy), commands.clone()));
-            } else {
-                break;
+    ///
+    /// This function computes every board the actual piece can lock in and the commands needed to get there.
+    ///
+    /// If holding is allowed, the boards reachable with the piece swapped in by a hold are considered as well.
+    ///
+    fn get_all_boards(&self, game: &Game) -> Vec<EndBoardWithMoves> {
+        let mut all_boards = self.get_all_boards_for_piece(game);
+
+        if game.can_hold {
+            let mut game_copy = game.clone();
+            if game_copy.hold() {
+                // The commands are performed from the back, so the hold has to be the last command.
+                for mut end_board in self.get_all_boards_for_piece(&game_copy).into_iter() {
+                    end_board.commands.push(Command::Hold);
+                    all_boards.push(end_board);
+                }
             }
-            piece_location -= 1;
         }
 
         all_boards
@@ -137,97 +257,225 @@ impl Autoplayer {
 
 
     ///
-    /// This function is used to compute the heuristics and evaluate the board given.
-    /// -2.5 * sum of all heights // 8.0 * completed rows // -4.5 * number of holes // 4.2 * touching walls // 4.0 * touching pieces
+    /// This function searches every state of the "actual_piece" reachable from where it is, breadth first, and returns the lock positions.
     ///
-    pub fn heuristic(&self, board: &mut Board) -> f64 {
-        let mut sum_of_heights = 0.0;
-        let mut completed_rows = 0.0;
-        let mut number_of_holes = 0.0;
-        let mut wall_touched = 0.0;
+    /// A state is the row, the column and the rotation of the piece. The states are changed by the movement and rotation rules of the game itself,
+    /// so tucks, slides under overhangs and spins with wall kicks are found as well.
+    /// A Down drops the piece until it rests, like gravity does it, so the rows it falls through count as one command.
+    /// A state that can't move down is a lock position, the first commands that reach it are the shortest ones.
+    /// Lock positions that cover the same cells in different states are only returned once.
+    ///
+    fn get_all_boards_for_piece(&self, game: &Game) -> Vec<EndBoardWithMoves> {
+        let mut all_boards: Vec<EndBoardWithMoves> = vec![];
+        if game.actual_piece.piece_type == PieceType::None || game.is_game_over() {
+            return all_boards;
+        }
 
-        // Compute the sum of heights, the number of holes and wall touched.
-        let mut heights = vec![0.0; board.columns];
-        let mut first_piece_found;
-        for column in 0..board.columns {
-            first_piece_found = false;
-            for row in 0..board.rows {
-                if (column == 0 || column == board.columns - 1) && board.board[row][column] != PieceType::None  {
-                    wall_touched += 1.0;
-                }
-                if row == board.rows - 1 && board.board[row][column] != PieceType::None  {
-                    wall_touched += 2.0;
-                }
-                if board.board[row][column] == PieceType::None {
-                    if first_piece_found {
-                        number_of_holes = number_of_holes + 1.0;
+        // Every state found so far, with the index of the state it was reached from and the command that reached it.
+        let mut game_copy = game.clone();
+        let mut states: Vec<(PieceState, Option<(usize, Command)>)> = vec![(PieceState::of(game), None)];
+        let mut reached = HashSet::new();
+        reached.insert(states[0].0.key());
+        let mut lock_positions = HashSet::new();
+
+        let mut index = 0;
+        while index < states.len() {
+            let state = states[index].0.clone();
+            for command in SEARCH_COMMANDS.iter() {
+                state.restore(&mut game_copy);
+                if self.apply(&mut game_copy, command) {
+                    if *command == Command::Down {
+                        while game_copy.move_in_direction(MoveDirection::Down) {}
                     }
-                } else {
-                    if !first_piece_found {
-                        heights[column] = board.rows as f64 - row as f64;
+                    if reached.insert(PieceState::key_of(&game_copy)) {
+                        states.push((PieceState::of(&game_copy), Some((index, command.clone()))));
+                    }
+                } else if *command == Command::Down {
+                    let coordinates = state.coordinates;
+                    let mut tiles: Vec<(usize, usize)> = state.piece.get_tiles(coordinates.x_coordinate, coordinates.y_coordinate).iter()
+                        .map(|tile| (tile.x_coordinate, tile.y_coordinate))
+                        .collect();
+                    tiles.sort_unstable();
+
+                    if lock_positions.insert(tiles) {
+                        all_boards.push(EndBoardWithMoves::new(game_copy.board.clone(), self.commands_to(&states, index)));
                     }
-                    first_piece_found = true;
                 }
             }
+            index += 1;
         }
 
-        // Get the sum of heights.
-        for height in heights {
-            sum_of_heights += height;
+        all_boards
+    }
+
+
+    ///
+    /// This function returns the commands that reach the state with the given index, the first command is the last one, like in "commands".
+    ///
+    /// A drop is written out as one Down per row the piece falls.
+    ///
+    fn commands_to(&self, states: &[(PieceState, Option<(usize, Command)>)], mut index: usize) -> Vec<Command> {
+        let mut commands = vec![];
+        while let Some((previous, ref command)) = states[index].1 {
+            if *command == Command::Down {
+                let rows = states[index].0.coordinates.x_coordinate - states[previous].0.coordinates.x_coordinate;
+                commands.extend(vec![Command::Down; rows]);
+            } else {
+                commands.push(command.clone());
+            }
+            index = previous;
         }
 
-        let mut row_completed;
+        commands
+    }
 
-        // Compute the number of touching pieces.
-        let mut touching_pieces_score = 0.0;
-        for x in 0..board.rows {
-            row_completed = true;
-            for y in 0..board.columns {
-                if board.board[x][y] != PieceType::None {
-                    if x < (board.rows - 1) {
-                        if board.board[x + 1][y] != PieceType::None {
-                            touching_pieces_score += 1.0;
-                        }
-                    }
-                    if x > 0 {
-                        if board.board[x - 1][y] != PieceType::None {
-                            touching_pieces_score += 1.0;
-                        }
-                    }
-                    if y < (board.columns - 1) {
-                        if board.board[x][y + 1] != PieceType::None {
-                            touching_pieces_score += 1.0;
-                        }
-                    }
-                    if y > 0 {
-                        if board.board[x][y - 1] != PieceType::None {
-                            touching_pieces_score += 1.0;
-                        }
-                    }
-                } else {
-                    row_completed = false;
+
+    ///
+    /// This function returns the board without the actual piece, the board the actual piece gets placed on.
+    ///
+    fn lift(&self, game: &Game) -> Board {
+        let mut board = game.board.clone();
+        let coordinates = game.actual_piece_coordinates;
+        board.remove_piece(&game.actual_piece, coordinates.x_coordinate, coordinates.y_coordinate);
+
+        board
+    }
+}
+
+
+
+///
+/// TESTS BEGIN HERE
+///
+#[cfg(test)]
+mod tests {
+    use autoplayer::Autoplayer;
+    use board::Board;
+    use game::Game;
+    use game::lock_delay::LockDelay;
+    use game::move_direction::MoveDirection;
+    use pieces;
+    use pieces::piece_type::PieceType;
+    use replay::input::Input;
+
+    fn spawned_game(seed: u64, preview_size: usize) -> Game {
+        let mut game = Game::with_seed(Board::new(20, 10), seed);
+        game.set_preview_size(preview_size);
+        game.step(MoveDirection::Down);
+        game
+    }
+
+    // Play like the GUI and the TUI, where gravity moves the piece down every few commands.
+    fn play_with_gravity(seed: u64, gravity_every: usize, piece_limit: usize) -> usize {
+        let mut game = Game::with_seed(Board::with_hidden_rows(20, 10, 20), seed);
+        game.lock_delay = LockDelay::autoplayer();
+        let mut auto_player = Autoplayer::with_lookahead(0);
+
+        let mut commands = 0;
+        while !game.is_game_over() && game.pieces_placed < piece_limit {
+            commands += 1;
+            if commands % gravity_every == 0 {
+                if Input::Gravity.apply(&mut game) {
+                    auto_player.compute_move(&mut game);
                 }
-            }
-            if row_completed {
-                completed_rows += 1.0;
+            } else {
+                auto_player.play_move(&mut game);
             }
         }
 
+        game.pieces_placed
+    }
 
+    fn commands_with_lookahead(game: &Game, lookahead: usize) -> Vec<String> {
+        let mut auto_player = Autoplayer::with_lookahead(lookahead);
+        auto_player.compute_move(&mut game.clone());
+        auto_player.commands.iter().map(|command| format!("{:?}", command)).collect()
+    }
 
-        sum_of_heights * -2.50 + completed_rows * 8.0 + number_of_holes * -4.50 + wall_touched * 4.20 + touching_pieces_score * 4.0
+    #[test]
+    fn lookahead_needs_preview_pieces() {
+        let game = spawned_game(5, 0);
+        assert_eq!(commands_with_lookahead(&game, 1), commands_with_lookahead(&game, 0));
+        assert_eq!(commands_with_lookahead(&game, 3), commands_with_lookahead(&game, 0));
     }
 
+    #[test]
+    fn lookahead_keeps_the_game_unchanged() {
+        let game = spawned_game(9, 5);
+        let mut searched = game.clone();
+        Autoplayer::with_lookahead(2).compute_move(&mut searched);
 
-    ///
-    /// This function is used to compute the out coming board by dropping the actual piece.
-    ///
-    pub fn drop(&self, game: &Game) -> Board {
-        let mut game_copy = game.clone();
+        assert_eq!(searched.board, game.board);
+        assert_eq!(searched.actual_piece_coordinates, game.actual_piece_coordinates);
+        assert_eq!(searched.preview(5).len(), 5);
+    }
 
-        while game_copy.move_in_direction(MoveDirection::Down) {}
+    #[test]
+    fn search_slides_under_overhangs() {
+        // A roof over the left seven columns of the two bottom rows, only the right columns are open from the top.
+        let mut game = spawned_game(3, 0);
+        for column in 0..7 {
+            game.board.board[17][column] = PieceType::Garbage;
+        }
+
+        let auto_player = Autoplayer::new();
+        let end_boards = auto_player.get_all_boards_for_piece(&game);
+        assert!(end_boards.iter().any(|end_board| end_board.board.board[19][0] != PieceType::None));
 
-        game_copy.board
+        // Every path leads to the board it was found with.
+        for end_board in end_boards.iter() {
+            let mut replayed = game.clone();
+            for command in end_board.commands.iter().rev() {
+                assert!(auto_player.apply(&mut replayed, command));
+            }
+            assert!(!replayed.can_move(MoveDirection::Down));
+            assert_eq!(replayed.board, end_board.board);
+        }
     }
-}
 
+    #[test]
+    fn tuck_with_gravity() {
+        // The O piece only gets under the roof over the two left columns by sliding in from the open columns next to it.
+        //      X X . . . . . . . .     row 17
+        //      . . . . X X X X X X     row 18
+        //      . . . . X X X X X X     row 19
+        // Gravity never ticks right after the piece rests here, that would lock it before the tuck like in every game without lock delay.
+        for gravity_every in [6, 8, 9].iter() {
+            let mut game = Game::new(Board::new(20, 10));
+            game.lock_delay = LockDelay::autoplayer();
+            game.actual_piece = pieces::get_o_piece();
+            game.next_piece = pieces::get_o_piece();
+            game.new_piece();
+            game.board.board[17][0] = PieceType::Garbage;
+            game.board.board[17][1] = PieceType::Garbage;
+            for row in 18..20 {
+                for column in 4..10 {
+                    game.board.board[row][column] = PieceType::Garbage;
+                }
+            }
+
+            let mut auto_player = Autoplayer::with_lookahead(0);
+            auto_player.compute_move(&mut game);
+
+            let mut commands = 0;
+            while game.pieces_placed == 0 {
+                commands += 1;
+                if commands % gravity_every == 0 {
+                    Input::Gravity.apply(&mut game);
+                } else {
+                    auto_player.play_move(&mut game);
+                }
+            }
+
+            assert_eq!(game.board.board[19][0], PieceType::O);
+            assert_eq!(game.board.board[18][1], PieceType::O);
+        }
+    }
+
+    #[test]
+    fn gravity_between_the_commands() {
+        for gravity_every in [30, 10, 5].iter() {
+            assert_eq!(play_with_gravity(1, *gravity_every, 100), 100);
+        }
+    }
+}
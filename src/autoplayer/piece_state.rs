@@ -0,0 +1,70 @@
+// Imports
+use game::Game;
+use pieces::piece::Piece;
+use pieces::rotation_state::RotationState;
+use utility::point::Point;
+
+
+// Make this struct cloneable and printable.
+// Represents where the actual piece of a game is, the states of the autoplayer's search.
+//
+// - piece:         the actual piece, rotated like it is on the board
+// - coordinates:   the coordinates of the actual piece
+// - rotation:      the rotation state of the actual piece
+#[derive(Debug, Clone)]
+pub struct PieceState {
+    pub piece: Piece,
+    pub coordinates: Point,
+    pub rotation: RotationState,
+}
+
+
+impl PieceState {
+    ///
+    /// This function returns the state of the actual piece of the given game.
+    ///
+    pub fn of(game: &Game) -> PieceState {
+        PieceState {
+            piece: game.actual_piece.clone(),
+            coordinates: game.actual_piece_coordinates,
+            rotation: game.actual_piece_rotation,
+        }
+    }
+
+
+    ///
+    /// This function returns the row, the column and the rotation, they identify the state.
+    ///
+    pub fn key(&self) -> (usize, usize, RotationState) {
+        (self.coordinates.x_coordinate, self.coordinates.y_coordinate, self.rotation)
+    }
+
+
+    ///
+    /// This function returns the key of the state the actual piece of the given game is in, without copying the piece.
+    ///
+    pub fn key_of(game: &Game) -> (usize, usize, RotationState) {
+        (game.actual_piece_coordinates.x_coordinate, game.actual_piece_coordinates.y_coordinate, game.actual_piece_rotation)
+    }
+
+
+    ///
+    /// This function moves the actual piece of the given game back into this state.
+    ///
+    /// The piece is only replaced if it is rotated differently, the rotation state determines the rotated piece.
+    ///
+    pub fn restore(&self, game: &mut Game) {
+        if PieceState::key_of(game) == self.key() {
+            return;
+        }
+
+        let coordinates = game.actual_piece_coordinates;
+        game.board.remove_piece(&game.actual_piece, coordinates.x_coordinate, coordinates.y_coordinate);
+        if game.actual_piece_rotation != self.rotation {
+            game.actual_piece = self.piece.clone();
+            game.actual_piece_rotation = self.rotation;
+        }
+        game.board.add_piece(&game.actual_piece, self.coordinates.x_coordinate, self.coordinates.y_coordinate);
+        game.actual_piece_coordinates = self.coordinates;
+    }
+}
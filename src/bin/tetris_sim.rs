@@ -0,0 +1,150 @@
+// Crates included. tetris => the game engine // rand => random base seed if none is given.
+extern crate tetris;
+extern crate rand;
+
+// Imports
+use std::time::Instant;
+
+use tetris::autoplayer::Autoplayer;
+use tetris::autoplayer::evaluator::{Evaluator, EvaluatorKind};
+use tetris::board::Board;
+use tetris::cli::Arguments;
+use tetris::game::{Game, MAX_PREVIEW_SIZE};
+use tetris::settings::Settings;
+use tetris::simulator::{self, SimulationResult, Statistics};
+
+
+///
+/// This struct represents the options of the simulator.
+///
+/// - games:       the number of games to play
+/// - seed:        the seed of the first game, game i is played with seed + i
+/// - rows:        the number of visible rows of the board
+/// - columns:     the number of columns of the board
+/// - hidden_rows: the number of hidden rows above the visible rows
+/// - piece_limit: the maximum number of pieces per game, unlimited if None
+/// - preview:     the number of preview pieces shown to the autoplayer
+/// - lookahead:   the number of preview pieces the autoplayer takes into account
+/// - evaluator:   the evaluation function of the autoplayer
+///
+struct Options {
+    games: usize,
+    seed: u64,
+    rows: usize,
+    columns: usize,
+    hidden_rows: usize,
+    piece_limit: Option<usize>,
+    preview: usize,
+    lookahead: usize,
+    evaluator: Box<dyn Evaluator>,
+}
+
+
+const USAGE: &str = "Usage: tetris-sim [OPTIONS]
+
+Lets the autoplayer play games as fast as possible, without a window.
+
+Options:
+    -n, --games <N>      number of games to play (default: 10)
+    -s, --seed <SEED>    seed of the first game, game i uses SEED + i (default: random)
+    -r, --rows <ROWS>    number of visible board rows (default: 20)
+    -c, --columns <COLS> number of board columns (default: 10)
+    --hidden-rows <ROWS> number of hidden rows above the visible board (default: 20)
+    -p, --pieces <N>     stop a game after N placed pieces, 0 means no limit (default: 10000)
+    --preview <N>        number of preview pieces, at most 6 (default: 5)
+    -l, --lookahead <N>  number of preview pieces the autoplayer plans with, 0 only places the
+                         actual piece, every preview piece makes a move 50 to 70 times slower
+                         (default: 0)
+    -e, --evaluator <NAME> evaluation function of the autoplayer: classic, dellacherie, el-tetris or
+                         linear (default: classic)
+    -w, --weights <FILE> load the evaluator and its weights from a weights file
+    -h, --help           print this help";
+
+
+fn main() {
+    let options = parse_options(Arguments::from_env(USAGE));
+
+    println!("{:>6} {:>20} {:>10} {:>12} {:>10}", "game", "seed", "rows", "points", "pieces");
+
+    let start = Instant::now();
+    let mut results: Vec<SimulationResult> = vec![];
+    for game in 0..options.games {
+        let seed = options.seed.wrapping_add(game as u64);
+        let mut new_game = Game::with_seed(Board::with_hidden_rows(options.rows, options.columns, options.hidden_rows), seed);
+        new_game.set_preview_size(options.preview);
+        let result = simulator::simulate_game(new_game, Autoplayer::with_evaluator(options.evaluator.clone(), options.lookahead), options.piece_limit);
+        println!("{:>6} {:>20} {:>10} {:>12} {:>10}", game + 1, result.seed, result.rows, result.points, result.pieces_placed);
+        results.push(result);
+    }
+    let elapsed = start.elapsed();
+
+    // Print the aggregate over all games.
+    let rows: Vec<usize> = results.iter().map(|result| result.rows).collect();
+    let points: Vec<usize> = results.iter().map(|result| result.points).collect();
+    let pieces: Vec<usize> = results.iter().map(|result| result.pieces_placed).collect();
+
+    println!();
+    println!("{:>8} {:>12} {:>12} {:>12}", "", "mean", "median", "max");
+    print_statistics("rows", &Statistics::new(&rows));
+    print_statistics("points", &Statistics::new(&points));
+    print_statistics("pieces", &Statistics::new(&pieces));
+    println!();
+    println!("{} games in {:.2}s", options.games, elapsed.as_secs_f64());
+}
+
+
+///
+/// This function prints one row of the aggregate table.
+///
+fn print_statistics(name: &str, statistics: &Statistics) {
+    println!("{:>8} {:>12.1} {:>12.1} {:>12}", name, statistics.mean, statistics.median, statistics.max);
+}
+
+
+///
+/// This function parses the command line arguments. It prints the usage and exits on invalid arguments.
+///
+fn parse_options(mut arguments: Arguments) -> Options {
+    let mut options = Options {
+        games: 10,
+        seed: rand::random(),
+        rows: 20,
+        columns: 10,
+        hidden_rows: Settings::new().hidden_rows,
+        piece_limit: Some(10_000),
+        preview: 5,
+        lookahead: 0,
+        evaluator: EvaluatorKind::Classic.evaluator(),
+    };
+
+    while let Some(arg) = arguments.next() {
+        match arg.as_str() {
+            "-n" | "--games" => options.games = arguments.value(&arg),
+            "-s" | "--seed" => options.seed = arguments.value(&arg),
+            "-r" | "--rows" => options.rows = arguments.value(&arg),
+            "-c" | "--columns" => options.columns = arguments.value(&arg),
+            "--hidden-rows" => options.hidden_rows = arguments.value(&arg),
+            "-p" | "--pieces" => {
+                let limit: usize = arguments.value(&arg);
+                options.piece_limit = if limit == 0 { None } else { Some(limit) };
+            }
+            "--preview" => options.preview = arguments.value(&arg),
+            "-l" | "--lookahead" => options.lookahead = arguments.value(&arg),
+            "-e" | "--evaluator" => options.evaluator = arguments.evaluator(&arg),
+            "-w" | "--weights" => options.evaluator = arguments.weights(&arg),
+            "-h" | "--help" => arguments.exit_with_help(),
+            _ => arguments.exit_with_error(&format!("Unknown option \"{}\".", arg)),
+        }
+    }
+
+    // The pieces spawn in the top rows and need at least 4 columns to be moved around.
+    if options.rows < 6 || options.columns < 4 {
+        arguments.exit_with_error("The board needs at least 6 rows and 4 columns.");
+    }
+    if options.preview > MAX_PREVIEW_SIZE {
+        arguments.exit_with_error(&format!("The preview shows at most {} pieces.", MAX_PREVIEW_SIZE));
+    }
+
+    options
+}
+
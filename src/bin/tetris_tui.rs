@@ -0,0 +1,111 @@
+// Crates included. tetris => the game engine and its terminal frontend // termion => raw mode and keyboard input.
+extern crate tetris;
+extern crate termion;
+
+// Imports
+use std::io::{self, Read, Write};
+use std::process;
+use std::thread;
+use std::time::Duration;
+
+use termion::event::Key;
+use termion::input::TermRead;
+use termion::raw::IntoRawMode;
+use termion::screen::AlternateScreen;
+
+use tetris::autoplayer::evaluator::{Evaluator, EvaluatorKind};
+use tetris::board::Board;
+use tetris::cli::Arguments;
+use tetris::replay::Replay;
+use tetris::tui::main_state::MainState;
+
+
+const USAGE: &str = "Usage: tetris-tui [OPTIONS]
+
+Plays Tetris in the terminal.
+
+Options:
+    -a, --autoplay              let the autoplayer play the game
+    -r, --rows <ROWS>           number of visible board rows (default: 20)
+    -c, --columns <COLS>        number of board columns (default: 10)
+        --hidden-rows <ROWS>    number of hidden rows above the visible board (default: 20)
+    -e, --evaluator <NAME>      evaluation function of the autoplayer: classic, dellacherie, el-tetris or
+                                linear (default: classic)
+    -w, --weights <FILE>        load the evaluator and its weights from a weights file
+    -h, --help                  print this help";
+
+
+fn main() {
+    let mut autoplay = false;
+    let mut rows = 20;
+    let mut columns = 10;
+    let mut hidden_rows = 20;
+    let mut evaluator = EvaluatorKind::Classic.evaluator();
+
+    let mut arguments = Arguments::from_env(USAGE);
+    while let Some(arg) = arguments.next() {
+        match arg.as_str() {
+            "-a" | "--autoplay" => autoplay = true,
+            "-r" | "--rows" => rows = arguments.value(&arg),
+            "-c" | "--columns" => columns = arguments.value(&arg),
+            "--hidden-rows" => hidden_rows = arguments.value(&arg),
+            "-e" | "--evaluator" => evaluator = arguments.evaluator(&arg),
+            "-w" | "--weights" => evaluator = arguments.weights(&arg),
+            "-h" | "--help" => arguments.exit_with_help(),
+            _ => arguments.exit_with_error(&format!("Unknown option \"{}\".", arg)),
+        }
+    }
+
+    // The pieces spawn in the top rows and need at least 4 columns to be moved around.
+    if rows < 6 || columns < 4 {
+        arguments.exit_with_error("The board needs at least 6 rows and 4 columns.");
+    }
+
+    if let Err(e) = run(autoplay, rows, columns, hidden_rows, evaluator) {
+        println!("Terminal Error in /bin/tetris_tui.rs: {}", e);
+        process::exit(1);
+    }
+}
+
+
+///
+/// This function plays games in raw mode on an alternate screen, until the player doesn't want to play again.
+///
+/// The terminal is restored as soon as the raw terminal and the alternate screen get dropped.
+///
+fn run(autoplay: bool, rows: usize, columns: usize, hidden_rows: usize, evaluator: Box<dyn Evaluator>) -> io::Result<()> {
+    let mut screen = AlternateScreen::from(io::stdout().into_raw_mode()?);
+    let mut keys = termion::async_stdin().keys();
+
+    // Loop every time restart was pressed in the game over screen.
+    loop {
+        let main_state = &mut MainState::new(autoplay, Board::with_hidden_rows(rows, columns, hidden_rows), evaluator.clone());
+        main_state.run(&mut keys, &mut screen)?;
+
+        // Save the replay of the game, so that it can be watched later in the GUI.
+        main_state.replay.save(&Replay::default_path())?;
+
+        // The game is over now, so we draw the game over screen.
+        main_state.tui.draw_game_over(&mut screen, main_state.game.rows, main_state.game.points, main_state.game.top_out)?;
+
+        // Start over if "r" was pressed.
+        if wait_for_key(&mut keys)? != Key::Char('r') {
+            break;
+        }
+    }
+
+    screen.flush()
+}
+
+
+///
+/// This function waits until a key was pressed on the non-blocking "keys" and returns it.
+///
+fn wait_for_key<R: Read>(keys: &mut termion::input::Keys<R>) -> io::Result<Key> {
+    loop {
+        if let Some(key) = keys.next() {
+            return key;
+        }
+        thread::sleep(Duration::from_millis(16));
+    }
+}
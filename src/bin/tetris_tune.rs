@@ -0,0 +1,176 @@
+// Crates included. tetris => the game engine and the tuner.
+extern crate tetris;
+
+// Imports
+use std::path::PathBuf;
+use std::thread;
+use std::time::Instant;
+
+use tetris::autoplayer::evaluator::{Evaluator, EvaluatorKind};
+use tetris::cli::Arguments;
+use tetris::tuner::{Tuner, TunerConfig};
+
+
+///
+/// This struct represents the options of the tuner binary.
+///
+/// - config:       the options of a new tuning, ignored when resuming
+/// - evaluator:    the evaluator whose weights are tuned, its weights are the start of the distributions
+/// - deviation:    the standard deviation the distributions start with
+/// - generations:  the total amount of generations, resumed tunings count the generations of the checkpoint
+/// - threads:      the amount of threads the games are played on
+/// - checkpoint:   the path of the checkpoint, written after every generation
+/// - resume:       indicates if the tuning continues from the checkpoint
+/// - output:       the path of the weights file with the best weights, written after every generation
+///
+struct Options {
+    config: TunerConfig,
+    evaluator: Box<dyn Evaluator>,
+    deviation: f64,
+    generations: usize,
+    threads: usize,
+    checkpoint: PathBuf,
+    resume: bool,
+    output: PathBuf,
+}
+
+
+const USAGE: &str = "Usage: tetris-tune [OPTIONS]
+
+Tunes the weights of an autoplayer evaluator with the noisy cross-entropy method.
+Every generation samples weight vectors, lets each of them play the same seeded games and fits the
+distributions to the best ones. The best weights are written as a weights file, which tetris-sim,
+tetris-tui and the settings can load.
+
+Options:
+    -e, --evaluator <NAME>   evaluator to tune: classic, dellacherie, el-tetris or linear (default: el-tetris)
+    -w, --weights <FILE>     start from the evaluator and weights of a weights file
+    -g, --generations <N>    total number of generations (default: 20)
+    -n, --population <N>     weight vectors per generation (default: 50)
+        --elite <N>          best weight vectors the distributions are fitted to (default: 10)
+        --games <N>          games per weight vector (default: 5)
+    -s, --seed <SEED>        seed of the games and of the sampling (default: 0)
+    -r, --rows <ROWS>        number of visible board rows (default: 20)
+    -c, --columns <COLS>     number of board columns (default: 10)
+        --hidden-rows <ROWS> number of hidden rows above the visible board (default: 20)
+    -p, --pieces <N>         stop a game after N placed pieces (default: 1000)
+        --preview <N>        number of preview pieces (default: 1)
+    -l, --lookahead <N>      number of preview pieces the autoplayer plans with (default: 0)
+        --deviation <D>      standard deviation of the weights at the start (default: 1.0)
+        --noise <V>          variance added after every generation, it decreases to 0 over
+                             --noise-generations generations (default: 1.0)
+        --noise-generations <N>  (default: 20)
+    -t, --threads <N>        number of threads (default: available cores)
+        --checkpoint <FILE>  checkpoint written after every generation (default: tune_checkpoint.json)
+        --resume             continue from the checkpoint, the options of the game and the sampling
+                             are taken from it
+    -o, --output <FILE>      weights file of the best weights (default: weights.toml)
+    -h, --help               print this help";
+
+
+fn main() {
+    let mut arguments = Arguments::from_env(USAGE);
+    let options = parse_options(&mut arguments);
+
+    let mut tuner = if options.resume {
+        match Tuner::load(&options.checkpoint) {
+            Ok(tuner) => tuner,
+            Err(e) => arguments.exit_with_error(&format!("Invalid checkpoint \"{}\": {}.", options.checkpoint.display(), e)),
+        }
+    } else {
+        Tuner::new(&*options.evaluator, options.config.clone(), options.deviation)
+    };
+
+    println!("Tuning the {} evaluator on {} threads, starting at generation {}.", tuner.evaluator.name(), options.threads, tuner.generation + 1);
+    println!();
+    println!("{:>10} {:>12} {:>12} {:>10}", "generation", "best", "best so far", "time");
+
+    while tuner.generation < options.generations {
+        let start = Instant::now();
+        let best = tuner.run_generation(options.threads);
+        let best_so_far = tuner.best.as_ref().map_or(0.0, |best| best.score);
+        println!("{:>10} {:>12.1} {:>12.1} {:>9.1}s", tuner.generation, best.score, best_so_far, start.elapsed().as_secs_f64());
+
+        if let Err(e) = tuner.save(&options.checkpoint) {
+            eprintln!("Checkpoint Error, {} in /bin/tetris_tune.rs", e);
+        }
+        if let Some(weights_file) = tuner.weights_file() {
+            if let Err(e) = weights_file.save(&options.output) {
+                eprintln!("Weights Error, {} in /bin/tetris_tune.rs", e);
+            }
+        }
+    }
+
+    println!();
+    println!("Best weights:");
+    if let Some(weights_file) = tuner.weights_file() {
+        for (name, weight) in weights_file.weights.iter() {
+            println!("{:>20} {:>12.4}", name, weight);
+        }
+        println!();
+        println!("Written to {}.", options.output.display());
+    }
+}
+
+
+///
+/// This function parses the command line arguments. It prints the usage and exits on invalid arguments.
+///
+fn parse_options(arguments: &mut Arguments) -> Options {
+    let mut options = Options {
+        config: TunerConfig::new(),
+        evaluator: EvaluatorKind::ElTetris.evaluator(),
+        deviation: 1.0,
+        generations: 20,
+        threads: thread::available_parallelism().map_or(1, |threads| threads.get()),
+        checkpoint: PathBuf::from("tune_checkpoint.json"),
+        resume: false,
+        output: PathBuf::from("weights.toml"),
+    };
+
+    while let Some(arg) = arguments.next() {
+        match arg.as_str() {
+            "-e" | "--evaluator" => options.evaluator = arguments.evaluator(&arg),
+            "-w" | "--weights" => options.evaluator = arguments.weights(&arg),
+            "-g" | "--generations" => options.generations = arguments.value(&arg),
+            "-n" | "--population" => options.config.population = arguments.value(&arg),
+            "--elite" => options.config.elite = arguments.value(&arg),
+            "--games" => options.config.games = arguments.value(&arg),
+            "-s" | "--seed" => options.config.seed = arguments.value(&arg),
+            "-r" | "--rows" => options.config.rows = arguments.value(&arg),
+            "-c" | "--columns" => options.config.columns = arguments.value(&arg),
+            "--hidden-rows" => options.config.hidden_rows = arguments.value(&arg),
+            "-p" | "--pieces" => options.config.piece_limit = arguments.value(&arg),
+            "--preview" => options.config.preview = arguments.value(&arg),
+            "-l" | "--lookahead" => options.config.lookahead = arguments.value(&arg),
+            "--deviation" => options.deviation = arguments.value(&arg),
+            "--noise" => options.config.noise = arguments.value(&arg),
+            "--noise-generations" => options.config.noise_generations = arguments.value(&arg),
+            "-t" | "--threads" => options.threads = arguments.value(&arg),
+            "--checkpoint" => options.checkpoint = PathBuf::from(arguments.value::<String>(&arg)),
+            "--resume" => options.resume = true,
+            "-o" | "--output" => options.output = PathBuf::from(arguments.value::<String>(&arg)),
+            "-h" | "--help" => arguments.exit_with_help(),
+            _ => arguments.exit_with_error(&format!("Unknown option \"{}\".", arg)),
+        }
+    }
+
+    // The pieces spawn in the top rows and need at least 4 columns to be moved around.
+    if options.config.rows < 6 || options.config.columns < 4 {
+        arguments.exit_with_error("The board needs at least 6 rows and 4 columns.");
+    }
+    if options.config.population == 0 || options.config.elite == 0 || options.config.elite > options.config.population {
+        arguments.exit_with_error("The elite needs at least 1 and at most --population weight vectors.");
+    }
+    if options.config.games == 0 || options.threads == 0 {
+        arguments.exit_with_error("There has to be at least 1 game and 1 thread.");
+    }
+    // NaN isn't finite, so it's rejected as well.
+    let valid = |value: f64| value.is_finite() && value >= 0.0;
+    if !valid(options.deviation) || !valid(options.config.noise) {
+        arguments.exit_with_error("The deviation and the noise have to be finite and can't be negative.");
+    }
+
+    options
+}
+
@@ -4,12 +4,15 @@ use pieces::piece::Piece;
 use pieces::piece_type::PieceType;
 
 
-// Make the struct cloneable, printable and comparable.
+// Make the struct cloneable, printable, comparable and serializable.
 // Board consists of rows, columns and the matrix implemented as a Vec<Vec<PieceType>>.
-#[derive(Clone, Debug, PartialEq)]
+// The first "hidden_rows" rows are a buffer above the visible field, "rows" counts them as well.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Board {
     pub rows: usize,
     pub columns: usize,
+    #[serde(default)]
+    pub hidden_rows: usize,
     pub board: Vec<Vec<PieceType>>
 }
 
@@ -17,14 +20,32 @@ pub struct Board {
 impl Board {
     // Initialize the board with PieceType::None.
     pub fn new(rows: usize, columns: usize) -> Board {
+        Board::with_hidden_rows(rows, columns, 0)
+    }
+
+
+    ///
+    /// This function creates a board with "visible_rows" rows and "hidden_rows" more rows above them.
+    ///
+    pub fn with_hidden_rows(visible_rows: usize, columns: usize, hidden_rows: usize) -> Board {
+        let rows = visible_rows + hidden_rows;
         Board {
             rows,
             columns,
+            hidden_rows,
             board: vec![vec![PieceType::None; columns]; rows]
         }
     }
 
 
+    ///
+    /// This function returns the amount of rows below the hidden rows.
+    ///
+    pub fn visible_rows(&self) -> usize {
+        self.rows - self.hidden_rows
+    }
+
+
     ///
     /// This function checks if we can add a piece at the given row, column.
     ///
@@ -54,10 +75,8 @@ impl Board {
         // If the board has PieceType::None values on every coordinate the piece has a true value in its body => the piece can be added to the board.
         for x in 0..rows {
             for y in 0..columns {
-                if piece.body.data[x][y] {
-                    if self.board[x + start_point.x_coordinate][y + start_point.y_coordinate] != PieceType::None {
-                        return false;
-                    }
+                if piece.body.data[x][y] && self.board[x + start_point.x_coordinate][y + start_point.y_coordinate] != PieceType::None {
+                    return false;
                 }
             }
         }
@@ -122,10 +141,8 @@ impl Board {
         // If every coordinate on the board where we want to remove the piece from has the piece_type of the piece given, return true and otherwise false.
         for x in 0..rows {
             for y in 0..columns {
-                if piece.body.data[x][y] {
-                    if self.board[x + start_point.x_coordinate][y + start_point.y_coordinate] != piece_type {
-                        return false;
-                    }
+                if piece.body.data[x][y] && self.board[x + start_point.x_coordinate][y + start_point.y_coordinate] != piece_type {
+                    return false;
                 }
             }
         }
@@ -185,7 +202,6 @@ impl Board {
         // Create a new vector where we store the new board in.
         let mut new_board = Vec::new();
         let mut row = rows - 1;
-        let mut column = 0;
 
         // Go through the actual board and ignore all rows where the row is contained in the complete_rows vector.
         for x in (0..rows).rev() {
@@ -194,7 +210,6 @@ impl Board {
             }
             for y in (0..columns).rev() {
                 new_board.push(self.board[x][y].clone());
-                column = column + 1;
             }
             row = (row + rows - 1) % rows;
         }
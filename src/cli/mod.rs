@@ -0,0 +1,130 @@
+// Imports
+use std::env;
+use std::path::Path;
+use std::process;
+use std::str::FromStr;
+use std::vec;
+
+use autoplayer::evaluator::{self, Evaluator, EvaluatorKind};
+
+
+// Represents the command line arguments of a binary, the options are read one after another.
+// Invalid arguments print the error and the usage of the binary and exit it.
+//
+// - args:      the arguments that weren't read yet
+// - usage:     the usage text of the binary
+pub struct Arguments {
+    args: vec::IntoIter<String>,
+    usage: &'static str,
+}
+
+
+impl Arguments {
+    ///
+    /// This function creates the arguments from the given ones, without the name of the binary.
+    ///
+    pub fn new(args: Vec<String>, usage: &'static str) -> Arguments {
+        Arguments {
+            args: args.into_iter(),
+            usage,
+        }
+    }
+
+
+    ///
+    /// This function returns the arguments the running binary was started with.
+    ///
+    pub fn from_env(usage: &'static str) -> Arguments {
+        Arguments::new(env::args().skip(1).collect(), usage)
+    }
+
+
+    ///
+    /// This function reads the value of the given option.
+    ///
+    pub fn value<T: FromStr>(&mut self, option: &str) -> T {
+        match self.args.next() {
+            Some(value) => match value.parse() {
+                Ok(value) => value,
+                Err(_) => self.exit_with_error(&format!("Invalid value \"{}\" for {}.", value, option)),
+            },
+            None => self.exit_with_error(&format!("Missing value for {}.", option)),
+        }
+    }
+
+
+    ///
+    /// This function reads the name of an evaluator given to the given option and returns the evaluator.
+    ///
+    pub fn evaluator(&mut self, option: &str) -> Box<dyn Evaluator> {
+        let name: String = self.value(option);
+        match EvaluatorKind::from_name(&name) {
+            Some(kind) => kind.evaluator(),
+            None => self.exit_with_error(&format!("Unknown evaluator \"{}\".", name)),
+        }
+    }
+
+
+    ///
+    /// This function reads the path of a weights file given to the given option and returns the evaluator of the file.
+    ///
+    pub fn weights(&mut self, option: &str) -> Box<dyn Evaluator> {
+        let path: String = self.value(option);
+        match evaluator::load_evaluator(Path::new(&path)) {
+            Ok(evaluator) => evaluator,
+            Err(e) => self.exit_with_error(&format!("Invalid weights file \"{}\": {}.", path, e)),
+        }
+    }
+
+
+    ///
+    /// This function prints the usage and exits the binary.
+    ///
+    pub fn exit_with_help(&self) -> ! {
+        println!("{}", self.usage);
+        process::exit(0);
+    }
+
+
+    ///
+    /// This function prints the error and the usage to stderr and exits the binary.
+    ///
+    pub fn exit_with_error(&self, error: &str) -> ! {
+        eprintln!("{}", error);
+        eprintln!();
+        eprintln!("{}", self.usage);
+        process::exit(1);
+    }
+}
+
+
+impl Iterator for Arguments {
+    type Item = String;
+
+    fn next(&mut self) -> Option<String> {
+        self.args.next()
+    }
+}
+
+
+///
+/// TESTS BEGIN HERE
+///
+#[cfg(test)]
+mod tests {
+    use autoplayer::evaluator::EvaluatorKind;
+    use cli::Arguments;
+
+    #[test]
+    fn options_and_their_values() {
+        let args = vec!["-r", "12", "--evaluator", "dellacherie", "-a"];
+        let mut arguments = Arguments::new(args.into_iter().map(String::from).collect(), "");
+
+        assert_eq!(arguments.next(), Some(String::from("-r")));
+        assert_eq!(arguments.value::<usize>("-r"), 12);
+        let option = arguments.next().unwrap();
+        assert_eq!(arguments.evaluator(&option).kind(), EvaluatorKind::Dellacherie);
+        assert_eq!(arguments.next(), Some(String::from("-a")));
+        assert_eq!(arguments.next(), None);
+    }
+}
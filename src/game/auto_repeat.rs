@@ -0,0 +1,278 @@
+// Imports
+use game::Game;
+use game::move_direction::MoveDirection;
+use replay::input::Input;
+
+
+// Make this struct copyable, cloneable, printable, comparable and serializable.
+// Represents how held keys repeat, stored as the [handling] table of the settings file.
+//
+// - das:               the Delayed Auto Shift, the time in milliseconds a horizontal key is held before it repeats
+// - arr:               the Auto Repeat Rate, the time in milliseconds between two repeats, 0 moves the piece to the wall at once
+// - soft_drop_factor:  how many times faster than gravity the piece falls while soft drop is held
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Handling {
+    pub das: f64,
+    pub arr: f64,
+    pub soft_drop_factor: f64,
+}
+
+
+impl Default for Handling {
+    fn default() -> Handling {
+        Handling::new()
+    }
+}
+
+
+impl Handling {
+    // The guideline values, 10 frames of DAS, 2 frames of ARR and 20 times faster soft drops.
+    pub fn new() -> Handling {
+        Handling {
+            das: 167.0,
+            arr: 33.0,
+            soft_drop_factor: 20.0,
+        }
+    }
+}
+
+
+// Make this struct cloneable, printable and comparable.
+// Represents a held key.
+//
+// - input:     the input that is repeated, Left, Right or SoftDrop
+// - time:      the time in seconds the key is held, or was held since it got active again
+// - repeats:   the amount of repeats performed so far
+#[derive(Debug, Clone, PartialEq)]
+struct HeldKey {
+    input: Input,
+    time: f64,
+    repeats: usize,
+}
+
+
+impl HeldKey {
+    fn new(input: Input) -> HeldKey {
+        HeldKey {
+            input,
+            time: 0.0,
+            repeats: 0,
+        }
+    }
+}
+
+
+// Make this struct cloneable, printable and comparable.
+// Repeats held keys by the game timer, so that the movement doesn't depend on the key repeat of the operating system.
+// The frontend reports every press and release and asks for the repeated inputs every frame.
+//
+// - handling:      the timings
+// - horizontal:    the held Left and Right keys, the last one pressed is the one that moves the piece
+// - soft_drop:     the held soft drop key
+#[derive(Debug, Clone, PartialEq)]
+pub struct AutoRepeat {
+    pub handling: Handling,
+    horizontal: Vec<HeldKey>,
+    soft_drop: Option<HeldKey>,
+}
+
+
+impl AutoRepeat {
+    pub fn new(handling: Handling) -> AutoRepeat {
+        AutoRepeat {
+            handling,
+            horizontal: vec![],
+            soft_drop: None,
+        }
+    }
+
+
+    ///
+    /// This function is called when the key of the input was pressed. The frontend applies the input once itself.
+    ///
+    pub fn press(&mut self, input: Input) {
+        match input {
+            Input::Left | Input::Right => {
+                self.horizontal.retain(|key| key.input != input);
+                self.horizontal.push(HeldKey::new(input));
+            }
+            Input::SoftDrop => self.soft_drop = Some(HeldKey::new(input)),
+            _ => {}
+        }
+    }
+
+
+    ///
+    /// This function is called when the key of the input was released.
+    ///
+    /// If the other horizontal key is still held, it moves the piece again, starting with a new delay.
+    ///
+    pub fn release(&mut self, input: Input) {
+        match input {
+            Input::Left | Input::Right => {
+                let was_active = self.horizontal.last().map(|key| key.input) == Some(input);
+                self.horizontal.retain(|key| key.input != input);
+                if was_active {
+                    if let Some(key) = self.horizontal.last_mut() {
+                        *key = HeldKey::new(key.input);
+                    }
+                }
+            }
+            Input::SoftDrop => self.soft_drop = None,
+            _ => {}
+        }
+    }
+
+
+    ///
+    /// This function forgets all held keys, e.g. when the game is paused and key releases could be missed.
+    ///
+    pub fn release_all(&mut self) {
+        self.horizontal.clear();
+        self.soft_drop = None;
+    }
+
+
+    ///
+    /// This function lets the given time in seconds pass and returns the inputs that are due.
+    ///
+    /// "gravity" is the time in seconds the piece needs to fall one row, the soft drop is "soft_drop_factor" times faster.
+    /// An ARR of 0 returns "max_moves" horizontal inputs, the frontend skips them with can_apply() at the wall.
+    /// No update returns more than "max_moves" inputs of a key, however small the ARR is.
+    ///
+    pub fn update(&mut self, elapsed: f64, gravity: f64, max_moves: usize) -> Vec<Input> {
+        let mut inputs = vec![];
+        let das = self.handling.das / 1000.0;
+        let arr = self.handling.arr / 1000.0;
+
+        if let Some(key) = self.horizontal.last_mut() {
+            key.time += elapsed;
+            if key.time >= das {
+                if arr <= 0.0 {
+                    inputs.extend(vec![key.input; max_moves]);
+                } else {
+                    let due = (((key.time - das) / arr) as usize).saturating_add(1);
+                    inputs.extend(vec![key.input; due.saturating_sub(key.repeats).min(max_moves)]);
+                    key.repeats = key.repeats.max(due);
+                }
+            }
+        }
+
+        if let Some(ref mut key) = self.soft_drop {
+            key.time += elapsed;
+            let interval = gravity / self.handling.soft_drop_factor.max(1.0);
+            let due = (key.time / interval) as usize;
+            inputs.extend(vec![key.input; due.saturating_sub(key.repeats).min(max_moves)]);
+            key.repeats = key.repeats.max(due);
+        }
+
+        inputs
+    }
+}
+
+
+///
+/// This function checks if a repeated input would move the actual piece.
+///
+/// Repeats stop at the wall and on the ground, so that a held key neither locks the piece nor records useless inputs.
+///
+pub fn can_apply(game: &mut Game, input: Input) -> bool {
+    match input {
+        Input::Left => game.can_move(MoveDirection::Left),
+        Input::Right => game.can_move(MoveDirection::Right),
+        Input::SoftDrop => game.can_move(MoveDirection::Down),
+        _ => true,
+    }
+}
+
+
+///
+/// TESTS BEGIN HERE
+///
+#[cfg(test)]
+mod tests {
+    use board::Board;
+    use game::Game;
+    use game::auto_repeat::{AutoRepeat, Handling, can_apply};
+    use replay::input::Input;
+
+    fn handling(das: f64, arr: f64) -> Handling {
+        Handling { das, arr, soft_drop_factor: 20.0 }
+    }
+
+    #[test]
+    fn das_and_arr() {
+        let mut auto_repeat = AutoRepeat::new(handling(100.0, 20.0));
+        auto_repeat.press(Input::Left);
+
+        // Nothing repeats before the delay is over, then one input every 20 ms.
+        assert!(auto_repeat.update(0.099, 1.0, 10).is_empty());
+        assert_eq!(auto_repeat.update(0.002, 1.0, 10), vec![Input::Left]);
+        assert!(auto_repeat.update(0.010, 1.0, 10).is_empty());
+        assert_eq!(auto_repeat.update(0.050, 1.0, 10), vec![Input::Left; 3]);
+
+        auto_repeat.release(Input::Left);
+        assert!(auto_repeat.update(1.0, 1.0, 10).is_empty());
+    }
+
+    #[test]
+    fn zero_arr_moves_to_the_wall() {
+        let mut auto_repeat = AutoRepeat::new(handling(50.0, 0.0));
+        auto_repeat.press(Input::Right);
+        assert!(auto_repeat.update(0.040, 1.0, 10).is_empty());
+        assert_eq!(auto_repeat.update(0.010, 1.0, 10), vec![Input::Right; 10]);
+
+        // The frontend stops at the wall.
+        let mut game = Game::with_seed(Board::new(20, 10), 1);
+        Input::Gravity.apply(&mut game);
+        let mut moves = 0;
+        for input in auto_repeat.update(0.016, 1.0, 10) {
+            if !can_apply(&mut game, input) {
+                break;
+            }
+            input.apply(&mut game);
+            moves += 1;
+        }
+        assert!(moves > 0 && moves < 10);
+        assert!(!can_apply(&mut game, Input::Right));
+        assert!(can_apply(&mut game, Input::Left));
+    }
+
+    #[test]
+    fn tiny_arr_is_capped() {
+        let mut auto_repeat = AutoRepeat::new(handling(0.0, 1e-9));
+        auto_repeat.press(Input::Left);
+        assert_eq!(auto_repeat.update(1.0, 1.0, 10), vec![Input::Left; 10]);
+        assert_eq!(auto_repeat.update(1.0, 1.0, 10), vec![Input::Left; 10]);
+    }
+
+    #[test]
+    fn last_pressed_direction_wins() {
+        let mut auto_repeat = AutoRepeat::new(handling(100.0, 20.0));
+        auto_repeat.press(Input::Left);
+        auto_repeat.update(0.2, 1.0, 10);
+        auto_repeat.press(Input::Right);
+        assert!(auto_repeat.update(0.05, 1.0, 10).is_empty());
+        assert_eq!(auto_repeat.update(0.05, 1.0, 10), vec![Input::Right]);
+
+        // Releasing Right lets the still held Left move again, after a new delay.
+        auto_repeat.release(Input::Right);
+        assert!(auto_repeat.update(0.09, 1.0, 10).is_empty());
+        assert_eq!(auto_repeat.update(0.02, 1.0, 10), vec![Input::Left]);
+    }
+
+    #[test]
+    fn soft_drop_factor() {
+        let mut auto_repeat = AutoRepeat::new(handling(100.0, 10.0));
+        auto_repeat.press(Input::SoftDrop);
+
+        // A gravity of 1 second per row falls a row every 50 ms with a factor of 20.
+        assert!(auto_repeat.update(0.049, 1.0, 20).is_empty());
+        assert_eq!(auto_repeat.update(0.001, 1.0, 20), vec![Input::SoftDrop]);
+        assert_eq!(auto_repeat.update(0.1, 1.0, 20), vec![Input::SoftDrop; 2]);
+
+        auto_repeat.release_all();
+        assert!(auto_repeat.update(1.0, 1.0, 20).is_empty());
+    }
+}
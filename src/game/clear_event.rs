@@ -0,0 +1,87 @@
+// Make this enum copyable, cloneable, printable, comparable and serializable.
+// Represents the kind of T-Spin a locked piece performed.
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub enum TSpin {
+    None,
+    Mini,
+    Full,
+}
+
+
+// Make this struct cloneable, printable, comparable and serializable.
+// Describes what happened when a piece locked, so that it can be displayed.
+//
+// - lines:             the amount of completed rows
+// - t_spin:            the kind of T-Spin that was performed
+// - back_to_back:      indicates if this was a difficult clear following another difficult clear
+// - combo:             the amount of line clears in a row before this one
+// - perfect_clear:     indicates if the board is completely empty after the clear
+// - points:            the points awarded for this clear
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ClearEvent {
+    pub lines: usize,
+    pub t_spin: TSpin,
+    pub back_to_back: bool,
+    pub combo: usize,
+    pub perfect_clear: bool,
+    pub points: usize,
+}
+
+
+impl ClearEvent {
+    pub fn new(lines: usize, t_spin: TSpin) -> ClearEvent {
+        ClearEvent {
+            lines,
+            t_spin,
+            back_to_back: false,
+            combo: 0,
+            perfect_clear: false,
+            points: 0,
+        }
+    }
+
+
+    ///
+    /// This function checks if the clear is a difficult one, which can be rewarded with a back to back bonus.
+    ///
+    /// Difficult clears are Tetrises and T-Spins that complete at least one row.
+    ///
+    pub fn is_difficult(&self) -> bool {
+        self.lines == 4 || (self.t_spin != TSpin::None && self.lines > 0)
+    }
+
+
+    ///
+    /// This function returns the name of the clear like "Back-to-Back T-Spin Double" or an empty string if nothing happened.
+    ///
+    pub fn name(&self) -> String {
+        let lines = match self.lines {
+            0 => "",
+            1 => "Single",
+            2 => "Double",
+            3 => "Triple",
+            _ => "Tetris",
+        };
+
+        let mut parts = vec![];
+        if self.back_to_back {
+            parts.push("Back-to-Back".to_string());
+        }
+        match self.t_spin {
+            TSpin::None => {},
+            TSpin::Mini => parts.push("T-Spin Mini".to_string()),
+            TSpin::Full => parts.push("T-Spin".to_string()),
+        }
+        if !lines.is_empty() {
+            parts.push(lines.to_string());
+        }
+        if self.perfect_clear {
+            parts.push("Perfect Clear".to_string());
+        }
+        if self.combo > 0 {
+            parts.push(format!("{} Combo", self.combo));
+        }
+
+        parts.join(" ")
+    }
+}
@@ -0,0 +1,127 @@
+// Make this enum copyable, cloneable, printable, comparable and serializable.
+// Represents the different lock delay rules.
+//
+// - Classic:      the piece locks as soon as it can't move down anymore
+// - MoveReset:    every move or rotation on the ground resets the lock delay, but only "max_resets" times
+// - Infinite:     every move or rotation on the ground resets the lock delay
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub enum LockDelayMode {
+    Classic,
+    MoveReset,
+    Infinite,
+}
+
+
+// The guideline values, 500 ms of lock delay and 15 resets.
+pub const DEFAULT_LOCK_DELAY: f64 = 0.5;
+pub const DEFAULT_MAX_RESETS: usize = 15;
+
+
+// Make this struct cloneable, printable, comparable and serializable.
+//
+// - mode:              the lock delay rules
+// - duration:          the time in seconds a piece can rest on the ground before it locks
+// - max_resets:        the amount of resets allowed by LockDelayMode::MoveReset
+// - timer:             the time in seconds the actual piece is resting on the ground
+// - resets:            the amount of resets the actual piece used so far
+// - lowest_row:        the lowest row the actual piece reached so far
+// - touched_ground:    indicates if the actual piece touched the ground since it reached the lowest row
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LockDelay {
+    pub mode: LockDelayMode,
+    pub duration: f64,
+    pub max_resets: usize,
+    pub timer: f64,
+    pub resets: usize,
+    pub lowest_row: usize,
+    pub touched_ground: bool,
+}
+
+
+impl LockDelay {
+    pub fn new(mode: LockDelayMode, duration: f64) -> LockDelay {
+        LockDelay {
+            mode,
+            duration,
+            max_resets: DEFAULT_MAX_RESETS,
+            timer: 0.0,
+            resets: 0,
+            lowest_row: 0,
+            touched_ground: false,
+        }
+    }
+
+
+    ///
+    /// This function creates the guideline lock delay.
+    ///
+    pub fn guideline() -> LockDelay {
+        LockDelay::new(LockDelayMode::MoveReset, DEFAULT_LOCK_DELAY)
+    }
+
+
+    ///
+    /// This function creates the lock delay of the autoplayer.
+    /// The autoplayer doesn't need time to slide its pieces, so it plays without a lock delay.
+    ///
+    pub fn autoplayer() -> LockDelay {
+        LockDelay::new(LockDelayMode::Classic, 0.0)
+    }
+
+
+    ///
+    /// This function resets the state for a new piece spawned at the given row.
+    ///
+    pub fn reset(&mut self, row: usize) {
+        self.timer = 0.0;
+        self.resets = 0;
+        self.lowest_row = row;
+        self.touched_ground = false;
+    }
+
+
+    ///
+    /// This function is called whenever the actual piece moved or rotated successfully and is now at the given row.
+    ///
+    /// Reaching a new lowest row resets everything, otherwise a move on the ground resets the timer according to the mode.
+    ///
+    pub fn piece_moved(&mut self, row: usize) {
+        if row > self.lowest_row {
+            self.reset(row);
+            return;
+        }
+
+        if !self.touched_ground {
+            return;
+        }
+
+        match self.mode {
+            LockDelayMode::Classic => { /* Nothing to reset here. */ },
+            LockDelayMode::MoveReset => {
+                if self.resets < self.max_resets {
+                    self.resets += 1;
+                    self.timer = 0.0;
+                }
+            },
+            LockDelayMode::Infinite => self.timer = 0.0,
+        }
+    }
+
+
+    ///
+    /// This function lets the given time in seconds pass.
+    ///
+    /// Returns {true} if the piece rested on the ground long enough and has to lock now, {false} otherwise.
+    ///
+    pub fn tick(&mut self, elapsed: f64, grounded: bool) -> bool {
+        if self.mode == LockDelayMode::Classic || !grounded {
+            return false;
+        }
+
+        self.touched_ground = true;
+        self.timer += elapsed;
+
+        // Once the resets are used up, the piece locks as soon as it touches the ground.
+        self.timer >= self.duration || (self.mode == LockDelayMode::MoveReset && self.resets >= self.max_resets)
+    }
+}
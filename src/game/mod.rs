@@ -1,50 +1,181 @@
 pub mod move_direction;
+pub mod lock_delay;
+pub mod clear_event;
+pub mod rules;
+pub mod auto_repeat;
+pub mod top_out;
 
 
 // Imports
+use std::collections::VecDeque;
+use std::iter;
+use std::mem;
+
+use rand::SeedableRng;
+use rand_pcg::Pcg32;
+
 use board::Board;
 use utility::point::Point;
+use utility::gravity_curve::GravityCurve;
 use pieces::piece::Piece;
+use pieces::generators::PieceGenerator;
+use pieces::generators::seven_bag_generator::SevenBagGenerator;
 use pieces::piece_type::PieceType;
+use pieces::rotation_state::RotationState;
+use pieces::wall_kicks;
 use self::move_direction::MoveDirection;
+use self::lock_delay::{LockDelay, LockDelayMode};
+use self::clear_event::{ClearEvent, TSpin};
+use self::top_out::TopOut;
 use pieces;
 
 
-// Make the game cloneable, printable and comparable.
-#[derive(Clone, Debug, PartialEq)]
+// The most pieces the preview can show.
+pub const MAX_PREVIEW_SIZE: usize = 6;
+
+
+// Games saved before the preview queue existed showed one piece.
+fn default_preview_size() -> usize {
+    1
+}
+
+
+// Make the game cloneable, printable and serializable.
+// The "next_piece" is the first piece of the preview, "upcoming_pieces" are the pieces after it.
+// "top_out" is the reason the game is over, None while it goes on.
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Game {
     pub board: Board,
     pub rows: usize,
     pub points: usize,
+    pub pieces_placed: usize,
+    pub level: usize,
+    pub starting_level: usize,
+    pub gravity_curve: GravityCurve,
     pub spawn_position: Point,
     pub actual_piece: Piece,
     pub next_piece: Piece,
+    #[serde(default)]
+    pub upcoming_pieces: VecDeque<Piece>,
+    #[serde(default = "default_preview_size")]
+    pub preview_size: usize,
+    pub held_piece: Piece,
+    pub can_hold: bool,
     pub piece_landed: bool,
     pub actual_piece_coordinates: Point,
+    pub actual_piece_rotation: RotationState,
+    #[serde(with = "::pieces::generators")]
+    pub piece_generator: Box<dyn PieceGenerator>,
+    pub lock_delay: LockDelay,
+    pub t_spin: TSpin,
+    pub back_to_back: bool,
+    pub combo: usize,
+    pub last_clear_event: Option<ClearEvent>,
+    #[serde(default)]
+    pub top_out: Option<TopOut>,
+    pub seed: u64,
+    pub rng: Pcg32,
 }
 
 
 impl Game {
+    // The guideline 7-bag generator and a random seed are used by default.
     pub fn new(board: Board) -> Game {
-        let columns = board.columns;
+        Game::with_seed(board, rand::random())
+    }
+
+
+    // Games with the same seed and the same inputs always end up identical.
+    pub fn with_seed(board: Board, seed: u64) -> Game {
+        Game::with_generator(board, Box::new(SevenBagGenerator::new()), seed)
+    }
+
+
+    pub fn with_generator(board: Board, piece_generator: Box<dyn PieceGenerator>, seed: u64) -> Game {
+        let spawn_position = Game::spawn_position_for(&board);
         Game {
             board,
             rows: 0,
             points: 0,
-            spawn_position: Point::new(2, columns / 2),
+            pieces_placed: 0,
+            level: 1,
+            starting_level: 1,
+            gravity_curve: GravityCurve::Guideline,
+            spawn_position,
             actual_piece: Piece::empty_piece(),
             next_piece: Piece::empty_piece(),
+            upcoming_pieces: VecDeque::new(),
+            preview_size: default_preview_size(),
+            held_piece: Piece::empty_piece(),
+            can_hold: true,
             piece_landed: false,
             actual_piece_coordinates: Point::empty_point(),
+            actual_piece_rotation: RotationState::Zero,
+            piece_generator,
+            lock_delay: LockDelay::guideline(),
+            t_spin: TSpin::None,
+            back_to_back: false,
+            combo: 0,
+            last_clear_event: None,
+            top_out: None,
+            seed,
+            rng: Pcg32::seed_from_u64(seed),
         }
     }
 
 
+    ///
+    /// This function returns where pieces spawn on the given board, derived from its size, always in the middle column.
+    ///
+    /// Without hidden rows the rotation point of a piece is placed in the third row.
+    /// With hidden rows the row is the last hidden row, the lowest block of a piece spawns in it, see spawn_coordinates().
+    ///
+    pub fn spawn_position_for(board: &Board) -> Point {
+        if board.hidden_rows == 0 {
+            Point::new(2, board.columns / 2)
+        } else {
+            Point::new(board.hidden_rows - 1, board.columns / 2)
+        }
+    }
+
+
+    ///
+    /// This function returns the coordinates of the rotation point the "actual_piece" spawns at.
+    ///
+    /// With hidden rows the piece spawns per guideline in the vanish zone above the visible board,
+    /// as far down as possible without a block in the visible rows.
+    ///
+    fn spawn_coordinates(&self) -> Point {
+        if self.board.hidden_rows == 0 {
+            return self.spawn_position;
+        }
+
+        let piece = &self.actual_piece;
+        let lowest_row = (0..piece.body.rows).rev()
+            .find(|&x| (0..piece.body.columns).any(|y| piece.body.data[x][y]))
+            .unwrap_or(0);
+
+        // The lowest block goes into the "spawn_position" row, but the top of the piece has to stay on the board.
+        let row = (self.spawn_position.x_coordinate + piece.point.x_coordinate).saturating_sub(lowest_row);
+        Point::new(row.max(piece.point.x_coordinate), self.spawn_position.y_coordinate)
+    }
+
+
+    ///
+    /// This function sets the level the game starts at.
+    ///
+    pub fn set_starting_level(&mut self, starting_level: usize) {
+        self.starting_level = starting_level;
+        self.level = self.starting_level + self.rows / 10;
+    }
+
+
     ///
     /// This function is called whenever a piece lands.
     ///
     /// It deletes completed rows, updates the completed rows counter "rows" and the points achieved so far "points".
-    /// After that, it adds the "actual_piece" at the spawn position (2, board.columns / 2) onto the board.
+    /// The result is stored as "last_clear_event". Every 10 completed rows the level goes up by one.
+    /// After that, it adds the "actual_piece" at the "spawn_position" onto the board.
     ///
     /// Returns {true} if the "actual_piece" could be added to the board, {false} otherwise.
     ///
@@ -52,23 +183,148 @@ impl Game {
         // Get the amount of deleted rows.
         let amount_of_deleted_rows = self.board.delete_complete_rows();
 
-        // Update the rows completed so far.
+        // Compute the points with the level before the rows are added.
+        let clear_event = self.score_clear(amount_of_deleted_rows, self.t_spin);
+
+        // Update the rows completed so far and the points accordingly.
         self.rows += amount_of_deleted_rows;
+        self.level = self.starting_level + self.rows / 10;
+        self.points += clear_event.points;
+        self.last_clear_event = Some(clear_event);
+
+        self.spawn_piece()
+    }
+
 
-        //Update the points accordingly.
-        match amount_of_deleted_rows {
-            0 => {/* Do nothing here. */},
-            1 => self.points += 100,
-            2 => self.points += 300,
-            3 => self.points += 500,
-            4 => self.points += 1000,
+    ///
+    /// This function computes the guideline score of a clear with the given amount of rows and the given t_spin.
+    ///
+    /// It updates the back to back and combo state and multiplies the points with the level:
+    ///     Single 100 // Double 300 // Triple 500 // Tetris 800
+    ///     T-Spin Mini 100 // T-Spin Mini Single 200 // T-Spin Mini Double 400
+    ///     T-Spin 400 // T-Spin Single 800 // T-Spin Double 1200 // T-Spin Triple 1600
+    ///     Back to back difficult clears are worth 1.5 times the points, every combo adds 50 points.
+    ///     Perfect clears add 800 // 1200 // 1800 // 2000 and 3200 for a back to back Tetris.
+    ///
+    fn score_clear(&mut self, lines: usize, t_spin: TSpin) -> ClearEvent {
+        let mut clear_event = ClearEvent::new(lines, t_spin);
+
+        let mut points = match (t_spin, lines) {
+            (TSpin::None, 0) => 0,
+            (TSpin::None, 1) => 100,
+            (TSpin::None, 2) => 300,
+            (TSpin::None, 3) => 500,
+            (TSpin::None, 4) => 800,
+            (TSpin::Mini, 0) => 100,
+            (TSpin::Mini, 1) => 200,
+            (TSpin::Mini, 2) => 400,
+            (TSpin::Full, 0) => 400,
+            (TSpin::Full, 1) => 800,
+            (TSpin::Full, 2) => 1200,
+            (TSpin::Full, 3) => 1600,
             _ => panic!("Error in /game/mod.rs, impossible amount of rows deleted.")
+        };
+
+        // A T-Spin without rows neither continues nor breaks a back to back chain.
+        if lines > 0 {
+            clear_event.back_to_back = clear_event.is_difficult() && self.back_to_back;
+            self.back_to_back = clear_event.is_difficult();
+        }
+        if clear_event.back_to_back {
+            points = points * 3 / 2;
         }
 
-        // Add the new piece onto the board and reset "piece_landed" to {false}.
-        if self.board.add_piece(&self.actual_piece, self.spawn_position.x_coordinate, self.spawn_position.y_coordinate) {
-            self.actual_piece_coordinates = self.spawn_position.clone();
+        // Every line clear in a row after the first one is a combo.
+        if lines > 0 {
+            clear_event.combo = self.combo;
+            points += 50 * self.combo;
+            self.combo += 1;
+        } else {
+            self.combo = 0;
+        }
+
+        // Check if the whole board is empty.
+        if lines > 0 && self.board.board.iter().all(|row| row.iter().all(|tile| *tile == PieceType::None)) {
+            clear_event.perfect_clear = true;
+            points += match lines {
+                1 => 800,
+                2 => 1200,
+                3 => 1800,
+                _ if clear_event.back_to_back => 3200,
+                _ => 2000,
+            };
+        }
+
+        // Level 0 has a gravity of its own, but it scores like level 1.
+        clear_event.points = points * self.level.max(1);
+        clear_event
+    }
+
+
+    ///
+    /// This function checks if the "actual_piece" performs a T-Spin after it was rotated with the kick of the given index.
+    ///
+    /// At least three of the four corners diagonal to the center of a TPiece have to be occupied (walls count as occupied).
+    /// It is a full T-Spin if both corners the TPiece points to are occupied or if the last kick was used, a mini one otherwise.
+    ///
+    fn detect_t_spin(&self, kick_index: usize) -> TSpin {
+        if self.actual_piece.piece_type != PieceType::T {
+            return TSpin::None;
+        }
+
+        // The rotation point of a TPiece is its center.
+        let row = self.actual_piece_coordinates.x_coordinate as isize;
+        let column = self.actual_piece_coordinates.y_coordinate as isize;
+        let is_occupied = |(row_offset, column_offset): (isize, isize)| {
+            let x = row + row_offset;
+            let y = column + column_offset;
+            x < 0 || y < 0 || x >= self.board.rows as isize || y >= self.board.columns as isize
+                || self.board.board[x as usize][y as usize] != PieceType::None
+        };
+
+        let top_left = (-1, -1);
+        let top_right = (-1, 1);
+        let bottom_left = (1, -1);
+        let bottom_right = (1, 1);
+
+        let occupied_corners = [top_left, top_right, bottom_left, bottom_right].iter().filter(|corner| is_occupied(**corner)).count();
+        if occupied_corners < 3 {
+            return TSpin::None;
+        }
+
+        let front_corners = match self.actual_piece_rotation {
+            RotationState::Zero => [top_left, top_right],
+            RotationState::Right => [top_right, bottom_right],
+            RotationState::Two => [bottom_left, bottom_right],
+            RotationState::Left => [top_left, bottom_left],
+        };
+
+        if front_corners.iter().all(|corner| is_occupied(*corner)) || kick_index == 4 {
+            TSpin::Full
+        } else {
+            TSpin::Mini
+        }
+    }
+
+
+    ///
+    /// This function adds the "actual_piece" at the spawn position onto the board and resets "piece_landed" to {false}.
+    ///
+    /// With hidden rows the piece drops one row right away per guideline, if nothing is in its way.
+    /// Returns {true} if the "actual_piece" could be added to the board, {false} otherwise. That is a block out, the game is over.
+    ///
+    fn spawn_piece(&mut self) -> bool {
+        let spawn = self.spawn_coordinates();
+        if self.board.add_piece(&self.actual_piece, spawn.x_coordinate, spawn.y_coordinate) {
+            self.actual_piece_coordinates = spawn;
+            self.actual_piece_rotation = RotationState::Zero;
             self.piece_landed = false;
+            self.t_spin = TSpin::None;
+            self.lock_delay.reset(spawn.x_coordinate);
+
+            if self.board.hidden_rows > 0 {
+                self.move_in_direction(MoveDirection::Down);
+            }
 
             // The "actual_piece" could be added, return {true}.
             return true;
@@ -76,79 +332,188 @@ impl Game {
 
 
         // The "actual_piece" couldn't be added, return {false}.
+        self.top_out = Some(TopOut::BlockOut);
         false
     }
 
 
+    ///
+    /// This function swaps the "actual_piece" with the "held_piece".
+    ///
+    /// If nothing is held yet, the "actual_piece" is stored and the "next_piece" takes its place.
+    /// Holding is only allowed once per drop, "can_hold" is enabled again as soon as a piece locks.
+    ///
+    /// Returns {true} if the pieces were swapped, {false} otherwise.
+    ///
+    pub fn hold(&mut self) -> bool {
+        if !self.can_hold || self.actual_piece.piece_type == PieceType::None {
+            return false;
+        }
+
+        // Remove the "actual_piece" off the board.
+        if !self.board.remove_piece(&self.actual_piece, self.actual_piece_coordinates.x_coordinate, self.actual_piece_coordinates.y_coordinate) {
+            return false;
+        }
+
+        // The held piece is always stored in its default orientation.
+        let held_piece = pieces::get_piece(&self.actual_piece.piece_type);
+        if self.held_piece.piece_type == PieceType::None {
+            self.actual_piece = self.take_next_piece();
+        } else {
+            self.actual_piece = self.held_piece.clone();
+        }
+        self.held_piece = held_piece;
+        self.can_hold = false;
+
+        // If the swapped in piece can't spawn, the game is over.
+        self.piece_landed = true;
+        self.spawn_piece();
+
+        true
+    }
+
+
     ///
     /// This function checks if the game is in an game over state.
     ///
-    /// If the "actual_piece" can't be added and the previous piece landed ("piece_landed") return {true}
-    /// return {false} otherwise.
+    /// Returns {true} after a block out, a lock out or a garbage out, see "top_out". Returns {false} otherwise.
     ///
     pub fn is_game_over(&self) -> bool {
-        !self.board.can_add_piece(&self.actual_piece, self.spawn_position.x_coordinate, self.spawn_position.y_coordinate) && self.piece_landed
+        self.top_out.is_some()
     }
 
 
     ///
-    /// This function is used to rotate the actual piece clockwise.
+    /// This function pushes the board up by one row per entry of "gap_columns" and fills the bottom with garbage rows.
     ///
-    /// First the "actual_piece" has to be removed. After that a clone of the "actual_piece" has to be rotated.
+    /// Every garbage row is full, besides the block in its gap column. The "actual_piece" is pushed up together with the board.
+    /// If a block or the "actual_piece" would be pushed above the top of the board, nothing is pushed and the game is over by a garbage out.
     ///
-    /// If the rotated piece can be added to the board, return {true} and set the "actual_piece" to the rotated one,
-    /// return {false} otherwise and add the "actual_piece" again to the board.
+    /// Returns {true} if the garbage was added, {false} otherwise.
     ///
-    pub fn rotate_piece_clockwise(&mut self) -> bool {
-        // Remove the "actual_piece" off the board.
-        if self.board.remove_piece(&self.actual_piece, self.actual_piece_coordinates.x_coordinate, self.actual_piece_coordinates.y_coordinate) {
-            // Create a clone of the "actual_piece", because we don't want to change it.
-            let mut rotated_piece = self.actual_piece.clone();
-            // Now compute the rotation.
-            rotated_piece.perform_clockwise_rotation();
+    pub fn add_garbage(&mut self, gap_columns: &[usize]) -> bool {
+        let amount = gap_columns.len();
+        let coordinates = self.actual_piece_coordinates;
+        let active = self.actual_piece.piece_type != PieceType::None && !self.piece_landed;
 
-            // Add the rotated piece to the board, if it can be added.
-            if self.board.add_piece(&rotated_piece, self.actual_piece_coordinates.x_coordinate, self.actual_piece_coordinates.y_coordinate) {
-                // Set the "actual_piece" to the rotated one and return {true}.
-                self.actual_piece = rotated_piece.clone();
-                return true;
-            } else {
-                // If we cant add the rotated piece, add the "actual_piece" again and return {false}.
-                self.board.add_piece(&self.actual_piece, self.actual_piece_coordinates.x_coordinate, self.actual_piece_coordinates.y_coordinate);
+        // The "actual_piece" is taken off the board while it is pushed.
+        let mut pushed_out = false;
+        if active {
+            self.board.remove_piece(&self.actual_piece, coordinates.x_coordinate, coordinates.y_coordinate);
+            let tiles = self.actual_piece.get_tiles(coordinates.x_coordinate, coordinates.y_coordinate);
+            pushed_out = tiles.iter().any(|tile| tile.x_coordinate < amount);
+        }
+        pushed_out = pushed_out || amount > self.board.rows
+            || self.board.board[..amount].iter().any(|row| row.iter().any(|tile| *tile != PieceType::None));
+
+        if pushed_out {
+            if active {
+                self.board.add_piece(&self.actual_piece, coordinates.x_coordinate, coordinates.y_coordinate);
             }
+            self.top_out = Some(TopOut::GarbageOut);
+            return false;
         }
 
-        false
+        let columns = self.board.columns;
+        self.board.board.drain(..amount);
+        for gap_column in gap_columns {
+            let mut row = vec![PieceType::Garbage; columns];
+            if *gap_column < columns {
+                row[*gap_column] = PieceType::None;
+            }
+            self.board.board.push(row);
+        }
+
+        if active {
+            self.actual_piece_coordinates = Point::new(coordinates.x_coordinate - amount, coordinates.y_coordinate);
+            self.board.add_piece(&self.actual_piece, self.actual_piece_coordinates.x_coordinate, coordinates.y_coordinate);
+            self.lock_delay.lowest_row = self.lock_delay.lowest_row.saturating_sub(amount);
+        }
+
+        true
+    }
+
+
+    ///
+    /// This function is used to rotate the actual piece clockwise.
+    ///
+    /// Returns {true} if the rotation (including a possible wall kick) was successful, {false} otherwise.
+    ///
+    pub fn rotate_piece_clockwise(&mut self) -> bool {
+        self.rotate_piece(true)
     }
 
 
     ///
     /// This function is used to rotate the actual piece counter-clockwise.
     ///
+    /// Returns {true} if the rotation (including a possible wall kick) was successful, {false} otherwise.
+    ///
+    pub fn rotate_piece_counter_clockwise(&mut self) -> bool {
+        self.rotate_piece(false)
+    }
+
+
+    ///
+    /// This function rotates the "actual_piece" according to the Super Rotation System.
+    ///
     /// First the "actual_piece" has to be removed. After that a clone of the "actual_piece" has to be rotated.
+    /// The rotated piece is then tried at every kick offset of the SRS kick table in order.
     ///
-    /// If the rotated piece can be added to the board, return {true} and set the "actual_piece" to the rotated one,
+    /// If the rotated piece can be added at one of the offsets, return {true} and set the "actual_piece" to the rotated one,
     /// return {false} otherwise and add the "actual_piece" again to the board.
     ///
-    pub fn rotate_piece_counter_clockwise(&mut self) -> bool {
+    fn rotate_piece(&mut self, clockwise: bool) -> bool {
+        let x_coordinate = self.actual_piece_coordinates.x_coordinate;
+        let y_coordinate = self.actual_piece_coordinates.y_coordinate;
+
         // Remove the "actual_piece" off the board.
-        if self.board.remove_piece(&self.actual_piece, self.actual_piece_coordinates.x_coordinate, self.actual_piece_coordinates.y_coordinate) {
-            // Create a clone of the "actual_piece", because we don't want to change it.
-            let mut rotated_piece = self.actual_piece.clone();
-            // Now compute the rotation.
+        if !self.board.remove_piece(&self.actual_piece, x_coordinate, y_coordinate) {
+            return false;
+        }
+
+        // Create a clone of the "actual_piece", because we don't want to change it.
+        // Now compute the rotation and the rotation state we end up in.
+        let mut rotated_piece = self.actual_piece.clone();
+        let from = self.actual_piece_rotation;
+        let to = if clockwise {
+            rotated_piece.perform_clockwise_rotation();
+            from.clockwise()
+        } else {
             rotated_piece.perform_counter_clockwise_rotation();
+            from.counter_clockwise()
+        };
+
+        // Correct the coordinates, so that the piece rotates around the center of its SRS bounding box.
+        let piece_type = &self.actual_piece.piece_type;
+        let (from_row, from_column) = wall_kicks::get_box_offset(piece_type, from);
+        let (to_row, to_column) = wall_kicks::get_box_offset(piece_type, to);
+        let base_row = x_coordinate as isize - from_row + to_row;
+        let base_column = y_coordinate as isize - from_column + to_column;
+
+        // Try every kick offset in order. The y-axis of the kick tables points up, the rows of the board go down.
+        for (kick_index, (kick_x, kick_y)) in wall_kicks::get_kick_offsets(piece_type, from, to).into_iter().enumerate() {
+            let row = base_row - kick_y;
+            let column = base_column + kick_x;
+            if row < 0 || column < 0 {
+                continue;
+            }
 
             // Add the rotated piece to the board, if it can be added.
-            if self.board.add_piece(&rotated_piece, self.actual_piece_coordinates.x_coordinate, self.actual_piece_coordinates.y_coordinate) {
-                // Set the "actual_piece" to the rotated one and return {true}.
-                self.actual_piece = rotated_piece.clone();
+            // Set the "actual_piece" to the rotated one and return {true}.
+            if self.board.add_piece(&rotated_piece, row as usize, column as usize) {
+                self.actual_piece = rotated_piece;
+                self.actual_piece_coordinates = Point::new(row as usize, column as usize);
+                self.actual_piece_rotation = to;
+                self.t_spin = self.detect_t_spin(kick_index);
+                self.lock_delay.piece_moved(row as usize);
                 return true;
-            } else {
-                // If we cant add the rotated piece, add the "actual_piece" again and return {false}.
-                self.board.add_piece(&self.actual_piece, self.actual_piece_coordinates.x_coordinate, self.actual_piece_coordinates.y_coordinate);
             }
         }
 
+        // If we cant add the rotated piece, add the "actual_piece" again and return {false}.
+        self.board.add_piece(&self.actual_piece, x_coordinate, y_coordinate);
+
         false
     }
 
@@ -177,7 +542,7 @@ impl Game {
                     x_coordinate += 1
                 },
                 MoveDirection::Left => {
-                    if y_coordinate <= 0 {
+                    if y_coordinate == 0 {
                         self.board.add_piece(&self.actual_piece, x_coordinate, y_coordinate);
                         return false;
                     }
@@ -195,6 +560,8 @@ impl Game {
             // Add the piece at the new coordinates, update the "actual_piece_coordinates" and return {true}.
             if self.board.add_piece(&self.actual_piece, x_coordinate, y_coordinate) {
                 self.actual_piece_coordinates = Point::new(x_coordinate, y_coordinate);
+                self.t_spin = TSpin::None;
+                self.lock_delay.piece_moved(x_coordinate);
                 return true;
             } else {
                 // Otherwise add the "actual_piece" back to the old location and return {false}.
@@ -206,28 +573,61 @@ impl Game {
     }
 
 
+    ///
+    /// This function returns the coordinates where the "actual_piece" would land, if it was dropped now.
+    ///
+    /// The board isn't changed, the tiles of the "actual_piece" itself are just treated as empty.
+    ///
+    pub fn ghost_position(&self) -> Point {
+        let coordinates = self.actual_piece_coordinates;
+        if self.actual_piece.piece_type == PieceType::None {
+            return coordinates;
+        }
+
+        let own_tiles = self.actual_piece.get_tiles(coordinates.x_coordinate, coordinates.y_coordinate);
+        let mut row = coordinates.x_coordinate;
+
+        // Go down as long as every tile one row below is on the board and empty (or a tile of the "actual_piece").
+        loop {
+            let tiles = self.actual_piece.get_tiles(row + 1, coordinates.y_coordinate);
+            let fits = tiles.iter().all(|tile| {
+                tile.x_coordinate < self.board.rows
+                    && (self.board.board[tile.x_coordinate][tile.y_coordinate] == PieceType::None || own_tiles.contains(tile))
+            });
+            if !fits {
+                break;
+            }
+            row += 1;
+        }
+
+        Point::new(row, coordinates.y_coordinate)
+    }
+
+
     ///
     /// This function is used to perform a logical game step in the given "move_direction".
     ///
     /// It returns {true} if a new piece was added and {false} otherwise.
     ///
     pub fn step(&mut self, move_direction: MoveDirection) -> bool {
-        // If the actual piece does not exist, create a new actual and next piece.
+        // If the actual piece does not exist, create a new actual and next piece and add the actual one onto the board.
         if self.actual_piece.piece_type == PieceType::None {
-            self.actual_piece = pieces::get_next_random_piece();
-            self.next_piece = pieces::get_next_random_piece();
+            self.actual_piece = self.piece_generator.next_piece(&mut self.rng);
+            self.next_piece = self.piece_generator.next_piece(&mut self.rng);
+            self.fill_preview();
+            self.piece_landed = true;
+            self.new_piece();
+            return true;
         }
 
         // If the "move_direction" is "Down", check if the piece has landed. (Move doesn't work.)
-        // If the "actual_piece" has landed, replace the "actual piece" with the "next piece".
-        // The "next_piece" gets replaced by a new random next piece.
+        // If the "actual_piece" has landed and there is no lock delay, replace the "actual piece" with the "next piece".
+        // The "next_piece" gets replaced by the next piece of the "piece_generator".
         // Call new_piece() after that, to update the amount of completed rows and points.
+        // With a lock delay the piece locks in update_lock_delay() instead.
         if move_direction == MoveDirection::Down {
-            if !self.move_in_direction(move_direction) {
-                self.piece_landed = true;
-                self.actual_piece = self.next_piece.clone();
-                self.next_piece = pieces::get_next_random_piece();
-                self.new_piece();
+            if !self.move_in_direction(move_direction) && self.lock_delay.mode == LockDelayMode::Classic {
+                self.lock_piece();
                 return true;
             }
         } else {
@@ -237,4 +637,654 @@ impl Game {
 
         false
     }
-}
\ No newline at end of file
+
+
+    ///
+    /// This function lets the given time in seconds pass for the lock delay.
+    ///
+    /// If the "actual_piece" rested on the ground for long enough, it gets locked.
+    /// It returns {true} if a new piece was added and {false} otherwise.
+    ///
+    pub fn update_lock_delay(&mut self, elapsed: f64) -> bool {
+        if self.actual_piece.piece_type == PieceType::None {
+            return false;
+        }
+
+        let grounded = !self.can_move_down();
+        if self.lock_delay.tick(elapsed, grounded) {
+            self.lock_piece();
+            return true;
+        }
+
+        false
+    }
+
+
+    ///
+    /// This function checks if the "actual_piece" could move down one row.
+    ///
+    fn can_move_down(&mut self) -> bool {
+        self.can_move(MoveDirection::Down)
+    }
+
+
+    ///
+    /// This function checks if the "actual_piece" could move one step in the given "move_direction", without moving it.
+    ///
+    pub fn can_move(&mut self, move_direction: MoveDirection) -> bool {
+        let x_coordinate = self.actual_piece_coordinates.x_coordinate;
+        let y_coordinate = self.actual_piece_coordinates.y_coordinate;
+
+        let (new_x_coordinate, new_y_coordinate) = match move_direction {
+            MoveDirection::Down => (x_coordinate + 1, y_coordinate),
+            MoveDirection::Left if y_coordinate == 0 => return false,
+            MoveDirection::Left => (x_coordinate, y_coordinate - 1),
+            MoveDirection::Right => (x_coordinate, y_coordinate + 1),
+        };
+
+        // Remove the "actual_piece" temporarily, so that it doesn't collide with itself.
+        if self.actual_piece.piece_type == PieceType::None || !self.board.remove_piece(&self.actual_piece, x_coordinate, y_coordinate) {
+            return false;
+        }
+        let can_move = self.board.can_add_piece(&self.actual_piece, new_x_coordinate, new_y_coordinate);
+        self.board.add_piece(&self.actual_piece, x_coordinate, y_coordinate);
+
+        can_move
+    }
+
+
+    ///
+    /// This function is used to perform a soft drop, a "Down" step initiated by the player.
+    ///
+    /// Every row the "actual_piece" moves down is worth 1 point.
+    /// It returns {true} if a new piece was added and {false} otherwise.
+    ///
+    pub fn soft_drop(&mut self) -> bool {
+        if self.actual_piece.piece_type != PieceType::None && self.move_in_direction(MoveDirection::Down) {
+            self.points += 1;
+            return false;
+        }
+
+        self.step(MoveDirection::Down)
+    }
+
+
+    ///
+    /// This function is used to perform a hard drop.
+    ///
+    /// The "actual_piece" is moved down to its resting row and locked immediately.
+    /// Every row the "actual_piece" moves down is worth 2 points.
+    /// It returns {true} if a new piece was added and {false} otherwise.
+    ///
+    pub fn hard_drop(&mut self) -> bool {
+        // Without an "actual_piece" there is nothing to drop, so just perform a normal step.
+        if self.actual_piece.piece_type == PieceType::None {
+            return self.step(MoveDirection::Down);
+        }
+
+        // Move down as long as possible and count the dropped rows.
+        let mut dropped_rows = 0;
+        while self.move_in_direction(MoveDirection::Down) {
+            dropped_rows += 1;
+        }
+        self.points += 2 * dropped_rows;
+
+        self.lock_piece();
+        true
+    }
+
+
+    ///
+    /// This function locks the "actual_piece" where it is.
+    ///
+    /// The "actual_piece" gets replaced by the "next_piece" and the "next_piece" by the next piece of the "piece_generator".
+    /// Holding is allowed again and "pieces_placed" goes up by one. Calls new_piece() after that, to update the amount of completed rows and points.
+    /// It is public so that replays can lock the piece at the same moment the lock delay did.
+    ///
+    pub fn lock_piece(&mut self) {
+        self.piece_landed = true;
+        self.can_hold = true;
+        self.pieces_placed += 1;
+
+        // A piece that locks completely inside the hidden rows is a lock out, the game is over.
+        let coordinates = self.actual_piece_coordinates;
+        let tiles = self.actual_piece.get_tiles(coordinates.x_coordinate, coordinates.y_coordinate);
+        if !tiles.is_empty() && tiles.iter().all(|tile| tile.x_coordinate < self.board.hidden_rows) {
+            self.top_out = Some(TopOut::LockOut);
+            return;
+        }
+
+        self.actual_piece = self.take_next_piece();
+        self.new_piece();
+    }
+
+
+    ///
+    /// This function returns the "next_piece" and moves every piece of the preview up by one.
+    ///
+    /// The preview is filled up with pieces of the "piece_generator" afterwards.
+    ///
+    fn take_next_piece(&mut self) -> Piece {
+        let next_piece = match self.upcoming_pieces.pop_front() {
+            Some(piece) => piece,
+            None => self.piece_generator.next_piece(&mut self.rng),
+        };
+        let piece = mem::replace(&mut self.next_piece, next_piece);
+        self.fill_preview();
+
+        piece
+    }
+
+
+    ///
+    /// This function draws pieces of the "piece_generator" until the preview holds "preview_size" pieces.
+    ///
+    /// The pieces are drawn in the same order, no matter how big the preview is, so the preview size doesn't change a game.
+    ///
+    fn fill_preview(&mut self) {
+        while self.upcoming_pieces.len() + 1 < self.preview_size {
+            let piece = self.piece_generator.next_piece(&mut self.rng);
+            self.upcoming_pieces.push_back(piece);
+        }
+    }
+
+
+    ///
+    /// This function sets the amount of pieces the preview shows, at most MAX_PREVIEW_SIZE.
+    ///
+    /// Pieces that were already drawn stay in the preview when it gets smaller.
+    ///
+    pub fn set_preview_size(&mut self, preview_size: usize) {
+        self.preview_size = preview_size.min(MAX_PREVIEW_SIZE);
+        if self.next_piece.piece_type != PieceType::None {
+            self.fill_preview();
+        }
+    }
+
+
+    ///
+    /// This function returns the next "n" pieces, but not more than the preview shows.
+    ///
+    /// The first one is the "next_piece". Before the first piece spawned the preview is empty.
+    ///
+    pub fn preview(&self, n: usize) -> Vec<&Piece> {
+        if self.next_piece.piece_type == PieceType::None {
+            return vec![];
+        }
+
+        iter::once(&self.next_piece).chain(self.upcoming_pieces.iter()).take(n.min(self.preview_size)).collect()
+    }
+}
+
+
+///
+/// TESTS BEGIN HERE
+///
+#[cfg(test)]
+mod tests {
+    use board::Board;
+    use game::Game;
+    use game::move_direction::MoveDirection;
+    use game::lock_delay::{LockDelay, LockDelayMode};
+    use game::clear_event::TSpin;
+    use game::top_out::TopOut;
+    use pieces;
+    use pieces::piece::Piece;
+    use pieces::piece_type::PieceType;
+    use pieces::rotation_state::RotationState;
+    use utility::point::Point;
+
+    fn game_with_piece(piece: Piece) -> Game {
+        let mut game = Game::new(Board::new(20, 10));
+        game.actual_piece = piece;
+        game.next_piece = pieces::get_o_piece();
+        game.new_piece();
+        game
+    }
+
+    #[test]
+    fn hard_drop_scores_and_locks() {
+        let mut game = game_with_piece(pieces::get_o_piece());
+        game.next_piece = pieces::get_t_piece();
+
+        // The O piece spawns with its bottom row at row 2 and drops down to row 19.
+        assert!(game.hard_drop());
+        assert_eq!(game.points, 2 * 17);
+        assert_eq!(game.board.board[19][game.spawn_position.y_coordinate], PieceType::O);
+        assert_eq!(game.actual_piece.piece_type, PieceType::T);
+        assert_eq!(game.pieces_placed, 1);
+    }
+
+    #[test]
+    fn soft_drop_scores_per_row() {
+        let mut game = game_with_piece(pieces::get_o_piece());
+        assert!(!game.soft_drop());
+        assert!(!game.soft_drop());
+        assert_eq!(game.points, 2);
+
+        // Gravity doesn't award any points.
+        assert!(!game.step(MoveDirection::Down));
+        assert_eq!(game.points, 2);
+    }
+
+    #[test]
+    fn hold_swaps_pieces_once_per_drop() {
+        let mut game = game_with_piece(pieces::get_t_piece());
+        game.next_piece = pieces::get_i_piece();
+
+        // The first hold stores the T piece and takes the next piece.
+        assert!(game.rotate_piece_clockwise());
+        assert!(game.hold());
+        assert_eq!(game.held_piece, pieces::get_t_piece());
+        assert_eq!(game.actual_piece, pieces::get_i_piece());
+        assert_eq!(game.actual_piece_coordinates, game.spawn_position);
+
+        // Holding again is only allowed after the piece locked.
+        assert!(!game.hold());
+        assert!(game.hard_drop());
+        let piece_type = game.actual_piece.piece_type.clone();
+        assert!(game.hold());
+        assert_eq!(game.actual_piece, pieces::get_t_piece());
+        assert_eq!(game.held_piece.piece_type, piece_type);
+    }
+
+    #[test]
+    fn same_seed_same_game() {
+        let play = |seed| {
+            let mut game = Game::with_seed(Board::new(20, 10), seed);
+            game.lock_delay = LockDelay::new(LockDelayMode::Classic, 0.0);
+            let mut piece_order = vec![];
+            for step in 0..2000 {
+                match step % 7 {
+                    0 => { game.rotate_piece_clockwise(); },
+                    1 => { game.step(MoveDirection::Left); },
+                    2 => { game.hold(); },
+                    3 => { game.step(MoveDirection::Right); },
+                    4 => { game.rotate_piece_counter_clockwise(); },
+                    _ => { game.step(MoveDirection::Down); },
+                }
+                piece_order.push(game.actual_piece.piece_type.clone());
+                if game.is_game_over() {
+                    break;
+                }
+            }
+            (game.board, game.points, game.rows, piece_order)
+        };
+
+        assert_eq!(play(7), play(7));
+        assert!(play(7) != play(8));
+    }
+
+    #[test]
+    fn preview_shows_the_pieces_that_spawn() {
+        let mut game = Game::with_seed(Board::new(20, 10), 5);
+        game.set_preview_size(4);
+        assert!(game.preview(4).is_empty());
+
+        game.step(MoveDirection::Down);
+        let preview: Vec<PieceType> = game.preview(10).iter().map(|piece| piece.piece_type.clone()).collect();
+        assert_eq!(preview.len(), 4);
+        assert_eq!(game.preview(2).len(), 2);
+
+        let mut spawned = vec![];
+        for _ in 0..4 {
+            game.hard_drop();
+            spawned.push(game.actual_piece.piece_type.clone());
+        }
+        assert_eq!(spawned, preview);
+        assert_eq!(game.preview(10).len(), 4);
+    }
+
+    #[test]
+    fn preview_size_does_not_change_the_pieces() {
+        let pieces = |preview_size| {
+            let mut game = Game::with_seed(Board::new(20, 10), 9);
+            game.set_preview_size(preview_size);
+            game.step(MoveDirection::Down);
+            let mut pieces = vec![];
+            for drop in 0..8 {
+                // Changing the size during the game keeps the pieces that were already drawn.
+                if drop == 4 {
+                    game.set_preview_size(6 - preview_size);
+                }
+                pieces.push(game.actual_piece.piece_type.clone());
+                game.hard_drop();
+            }
+            pieces
+        };
+
+        assert!(Game::with_seed(Board::new(20, 10), 9).preview(6).is_empty());
+        assert_eq!(pieces(0), pieces(1));
+        assert_eq!(pieces(1), pieces(6));
+
+        let mut game = Game::with_seed(Board::new(20, 10), 9);
+        game.set_preview_size(0);
+        game.step(MoveDirection::Down);
+        assert!(game.preview(6).is_empty());
+        game.set_preview_size(10);
+        assert_eq!(game.preview(10).len(), 6);
+    }
+
+    #[test]
+    fn pieces_spawn_in_the_hidden_rows() {
+        let mut game = Game::with_seed(Board::with_hidden_rows(20, 9, 20), 1);
+        assert_eq!(game.board.rows, 40);
+        assert_eq!(game.board.visible_rows(), 20);
+        assert_eq!(game.spawn_position, Point::new(19, 4));
+
+        // The piece drops one row right away, so that its lowest block is in the first visible row.
+        game.step(MoveDirection::Down);
+        let coordinates = game.actual_piece_coordinates;
+        let tiles = game.actual_piece.get_tiles(coordinates.x_coordinate, coordinates.y_coordinate);
+        assert_eq!(tiles.iter().map(|tile| tile.x_coordinate).max(), Some(20));
+
+        game.hard_drop();
+        assert!(game.board.board[39].iter().any(|tile| *tile != PieceType::None));
+        assert!(!game.is_game_over());
+    }
+
+    #[test]
+    fn block_out() {
+        let mut game = Game::with_seed(Board::new(20, 10), 1);
+        for row in 0..20 {
+            game.board.board[row][5] = PieceType::Garbage;
+        }
+
+        game.step(MoveDirection::Down);
+        assert!(game.is_game_over());
+        assert_eq!(game.top_out, Some(TopOut::BlockOut));
+    }
+
+    #[test]
+    fn lock_out() {
+        let mut game = Game::with_seed(Board::with_hidden_rows(20, 10, 4), 1);
+        game.lock_delay = LockDelay::new(LockDelayMode::Classic, 0.0);
+        for row in 4..24 {
+            for column in 1..10 {
+                game.board.board[row][column] = PieceType::Garbage;
+            }
+        }
+
+        // The piece spawns above the full visible rows and locks in the hidden rows.
+        game.step(MoveDirection::Down);
+        assert!(!game.is_game_over());
+        game.hard_drop();
+        assert_eq!(game.top_out, Some(TopOut::LockOut));
+    }
+
+    #[test]
+    fn garbage_pushes_the_board_up() {
+        let mut game = Game::with_seed(Board::with_hidden_rows(20, 10, 20), 1);
+        game.step(MoveDirection::Down);
+        let coordinates = game.actual_piece_coordinates;
+
+        assert!(game.add_garbage(&[3, 7]));
+        assert_eq!(game.actual_piece_coordinates, Point::new(coordinates.x_coordinate - 2, coordinates.y_coordinate));
+        assert_eq!(game.board.board[38][3], PieceType::None);
+        assert_eq!(game.board.board[39][7], PieceType::None);
+        assert_eq!(game.board.board[39].iter().filter(|tile| **tile == PieceType::Garbage).count(), 9);
+
+        // Clearing a garbage row works like any other row.
+        game.board.board[39][7] = PieceType::Garbage;
+        assert_eq!(game.board.delete_complete_rows(), 1);
+
+        // A block in the top row would be pushed off the board.
+        game.board.board[0][0] = PieceType::Garbage;
+        assert!(!game.add_garbage(&[0]));
+        assert_eq!(game.top_out, Some(TopOut::GarbageOut));
+    }
+
+    #[test]
+    fn classic_locks_on_step() {
+        let mut game = game_with_piece(pieces::get_o_piece());
+        game.lock_delay = LockDelay::new(LockDelayMode::Classic, 0.0);
+        while game.move_in_direction(MoveDirection::Down) {}
+        assert!(!game.update_lock_delay(10.0));
+        assert!(game.step(MoveDirection::Down));
+    }
+
+    #[test]
+    fn lock_delay_locks_after_duration() {
+        let mut game = game_with_piece(pieces::get_o_piece());
+        while game.move_in_direction(MoveDirection::Down) {}
+
+        // Moving down on the ground doesn't lock the piece.
+        assert!(!game.step(MoveDirection::Down));
+        assert!(!game.update_lock_delay(0.3));
+        assert!(game.update_lock_delay(0.3));
+        assert_eq!(game.board.board[19][game.spawn_position.y_coordinate], PieceType::O);
+    }
+
+    #[test]
+    fn lock_delay_is_not_running_in_the_air() {
+        let mut game = game_with_piece(pieces::get_o_piece());
+        assert!(!game.update_lock_delay(10.0));
+    }
+
+    #[test]
+    fn move_reset_is_capped() {
+        let mut game = game_with_piece(pieces::get_o_piece());
+        while game.move_in_direction(MoveDirection::Down) {}
+
+        // Every move resets the lock delay, until all resets are used up.
+        for reset in 0..15 {
+            assert!(!game.update_lock_delay(0.4));
+            let direction = if reset % 2 == 0 { MoveDirection::Left } else { MoveDirection::Right };
+            assert!(game.move_in_direction(direction));
+        }
+        assert!(game.update_lock_delay(0.01));
+    }
+
+    #[test]
+    fn infinite_lock_delay_resets_forever() {
+        let mut game = game_with_piece(pieces::get_o_piece());
+        game.lock_delay = LockDelay::new(LockDelayMode::Infinite, 0.5);
+        while game.move_in_direction(MoveDirection::Down) {}
+
+        for _ in 0..100 {
+            assert!(!game.update_lock_delay(0.4));
+            assert!(game.rotate_piece_clockwise());
+        }
+        assert!(game.update_lock_delay(0.5));
+    }
+
+    #[test]
+    fn ghost_position_matches_hard_drop() {
+        let mut game = game_with_piece(pieces::get_l_piece());
+        for column in 0..6 {
+            game.board.board[15][column] = PieceType::Z;
+        }
+        let board = game.board.clone();
+
+        // The ghost position doesn't change the game.
+        let ghost = game.ghost_position();
+        assert_eq!(game.board, board);
+
+        while game.move_in_direction(MoveDirection::Down) {}
+        assert_eq!(game.actual_piece_coordinates, ghost);
+        assert_eq!(ghost, Point::new(14, game.spawn_position.y_coordinate));
+    }
+
+    fn fill_rows(game: &mut Game, rows: &[usize], gap_column: usize) {
+        for row in rows {
+            for column in 0..game.board.columns {
+                if column != gap_column {
+                    game.board.board[*row][column] = PieceType::Z;
+                }
+            }
+        }
+    }
+
+    fn drop_i_piece_at_left_wall(game: &mut Game) {
+        assert_eq!(game.actual_piece.piece_type, PieceType::I);
+        game.next_piece = pieces::get_i_piece();
+        assert!(game.rotate_piece_clockwise());
+        while game.move_in_direction(MoveDirection::Left) {}
+        game.hard_drop();
+    }
+
+    #[test]
+    fn t_spin_double() {
+        let mut game = game_with_piece(pieces::get_o_piece());
+        fill_rows(&mut game, &[18], 4);
+        for column in [3, 4, 5].iter() {
+            game.board.board[18][*column] = PieceType::None;
+        }
+        fill_rows(&mut game, &[19], 4);
+        game.board.board[17][3] = PieceType::Z;
+
+        // Put a TPiece pointing right into the slot.
+        let coordinates = game.actual_piece_coordinates;
+        game.board.remove_piece(&game.actual_piece.clone(), coordinates.x_coordinate, coordinates.y_coordinate);
+        let mut piece = pieces::get_t_piece();
+        piece.perform_clockwise_rotation();
+        assert!(game.board.add_piece(&piece, 18, 4));
+        game.actual_piece = piece;
+        game.actual_piece_coordinates = Point::new(18, 4);
+        game.actual_piece_rotation = RotationState::Right;
+
+        assert!(game.rotate_piece_clockwise());
+        assert_eq!(game.t_spin, TSpin::Full);
+        assert!(game.hard_drop());
+
+        let clear_event = game.last_clear_event.clone().unwrap();
+        assert_eq!(clear_event.name(), "T-Spin Double");
+        assert_eq!(clear_event.points, 1200);
+        assert_eq!(game.points, 1200);
+        assert_eq!(game.board.board[19][3], PieceType::Z);
+    }
+
+    #[test]
+    fn moving_after_rotation_is_no_t_spin() {
+        let mut game = game_with_piece(pieces::get_t_piece());
+        assert!(game.rotate_piece_clockwise());
+        assert!(game.move_in_direction(MoveDirection::Left));
+        assert_eq!(game.t_spin, TSpin::None);
+    }
+
+    #[test]
+    fn back_to_back_tetris() {
+        let mut game = game_with_piece(pieces::get_i_piece());
+        game.board.board[15][5] = PieceType::Z;
+        fill_rows(&mut game, &[16, 17, 18, 19], 0);
+        drop_i_piece_at_left_wall(&mut game);
+        let clear_event = game.last_clear_event.clone().unwrap();
+        assert_eq!(clear_event.name(), "Tetris");
+        assert_eq!(clear_event.points, 800);
+
+        game.board.board[19][0] = PieceType::Z;
+        fill_rows(&mut game, &[15, 16, 17, 18], 0);
+        drop_i_piece_at_left_wall(&mut game);
+        let clear_event = game.last_clear_event.clone().unwrap();
+        assert_eq!(clear_event.name(), "Back-to-Back Tetris 1 Combo");
+        assert_eq!(clear_event.points, 1200 + 50);
+    }
+
+    #[test]
+    fn perfect_clear() {
+        let mut game = game_with_piece(pieces::get_i_piece());
+        fill_rows(&mut game, &[16, 17, 18, 19], 0);
+        drop_i_piece_at_left_wall(&mut game);
+        let clear_event = game.last_clear_event.clone().unwrap();
+        assert!(clear_event.perfect_clear);
+        assert_eq!(clear_event.points, 800 + 2000);
+    }
+
+    #[test]
+    fn level_goes_up_every_ten_rows() {
+        let mut game = game_with_piece(pieces::get_i_piece());
+        game.set_starting_level(5);
+        assert_eq!(game.level, 5);
+        game.board.board[5][9] = PieceType::Z;
+
+        // The level only goes up after the third tetris, when 10 rows are completed.
+        for tetris in 0..3 {
+            game.board.board[19][0] = PieceType::None;
+            fill_rows(&mut game, &[16, 17, 18, 19], 0);
+            drop_i_piece_at_left_wall(&mut game);
+            assert_eq!(game.rows, 4 * (tetris + 1));
+        }
+        assert_eq!(game.level, 6);
+        assert_eq!(game.last_clear_event.clone().unwrap().points, (1200 + 100) * 5);
+    }
+
+    #[test]
+    fn level_zero_scores_like_level_one() {
+        let mut game = game_with_piece(pieces::get_i_piece());
+        game.set_starting_level(0);
+        game.board.board[15][5] = PieceType::Z;
+        fill_rows(&mut game, &[16, 17, 18, 19], 0);
+        drop_i_piece_at_left_wall(&mut game);
+        assert_eq!(game.last_clear_event.clone().unwrap().points, 800);
+    }
+
+    #[test]
+    fn spawn_rotation_state() {
+        for number in 0..7 {
+            let game = game_with_piece(pieces::get_piece_by_number(number));
+            assert_eq!(game.actual_piece_rotation, RotationState::Zero);
+        }
+    }
+
+    #[test]
+    fn full_rotation_keeps_position() {
+        for piece in [pieces::get_i_piece(), pieces::get_o_piece(), pieces::get_j_piece()].iter().cloned() {
+            let mut game = game_with_piece(piece);
+            let board = game.board.clone();
+            for _ in 0..4 {
+                assert!(game.rotate_piece_clockwise());
+            }
+            assert_eq!(game.board, board);
+            for _ in 0..4 {
+                assert!(game.rotate_piece_counter_clockwise());
+            }
+            assert_eq!(game.board, board);
+        }
+    }
+
+    #[test]
+    fn o_piece_does_not_move() {
+        let mut game = game_with_piece(pieces::get_o_piece());
+        let board = game.board.clone();
+        assert!(game.rotate_piece_clockwise());
+        assert_eq!(game.board, board);
+        assert_eq!(game.actual_piece_rotation, RotationState::Right);
+    }
+
+    #[test]
+    fn i_piece_kicks_off_the_left_wall() {
+        let mut game = game_with_piece(pieces::get_i_piece());
+        assert!(game.rotate_piece_clockwise());
+        while game.move_in_direction(MoveDirection::Left) {}
+
+        // The vertical I piece at the wall can only rotate by kicking two columns to the right.
+        assert!(game.rotate_piece_clockwise());
+        assert_eq!(game.actual_piece_rotation, RotationState::Two);
+        assert_eq!(game.actual_piece_coordinates, Point::new(3, 2));
+        for column in 0..4 {
+            assert_eq!(game.board.board[3][column], PieceType::I);
+        }
+    }
+
+    #[test]
+    fn blocked_rotation_fails() {
+        let mut game = game_with_piece(pieces::get_i_piece());
+        assert!(game.rotate_piece_clockwise());
+        let coordinates = game.actual_piece_coordinates;
+
+        // Fill everything but the column of the I piece.
+        for row in 0..game.board.rows {
+            for column in 0..game.board.columns {
+                if column != coordinates.y_coordinate {
+                    game.board.board[row][column] = PieceType::Z;
+                }
+            }
+        }
+        let board = game.board.clone();
+
+        assert!(!game.rotate_piece_clockwise());
+        assert_eq!(game.board, board);
+        assert_eq!(game.actual_piece_coordinates, coordinates);
+        assert_eq!(game.actual_piece_rotation, RotationState::Right);
+    }
+}
@@ -0,0 +1,116 @@
+// Imports
+use board::Board;
+use game::Game;
+use game::lock_delay::{LockDelay, LockDelayMode};
+use pieces::generators;
+use utility::gravity_curve::GravityCurve;
+
+
+// Replays recorded before the preview queue existed showed one piece.
+fn default_preview_size() -> usize {
+    1
+}
+
+
+// Make this struct cloneable, printable, comparable and serializable.
+// Represents the rules a game is played with, everything besides the seed that is needed to reproduce a game.
+//
+// - rows, columns:     the size of the visible board
+// - hidden_rows:       the amount of rows above the visible board
+// - generator:         the name of the piece generator, see pieces::generators::get_generator
+// - lock_delay_mode:   the lock delay rules
+// - lock_delay:        the lock delay in seconds
+// - max_resets:        the amount of lock delay resets allowed by LockDelayMode::MoveReset
+// - gravity_curve:     the gravity curve
+// - starting_level:    the level the game starts at
+// - preview_size:      the amount of pieces the preview shows
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Rules {
+    pub rows: usize,
+    pub columns: usize,
+    #[serde(default)]
+    pub hidden_rows: usize,
+    pub generator: String,
+    pub lock_delay_mode: LockDelayMode,
+    pub lock_delay: f64,
+    pub max_resets: usize,
+    pub gravity_curve: GravityCurve,
+    pub starting_level: usize,
+    #[serde(default = "default_preview_size")]
+    pub preview_size: usize,
+}
+
+
+impl Rules {
+    ///
+    /// This function returns the rules the given game is played with.
+    ///
+    pub fn from_game(game: &Game) -> Rules {
+        Rules {
+            rows: game.board.visible_rows(),
+            columns: game.board.columns,
+            hidden_rows: game.board.hidden_rows,
+            generator: game.piece_generator.name().to_string(),
+            lock_delay_mode: game.lock_delay.mode,
+            lock_delay: game.lock_delay.duration,
+            max_resets: game.lock_delay.max_resets,
+            gravity_curve: game.gravity_curve,
+            starting_level: game.starting_level,
+            preview_size: game.preview_size,
+        }
+    }
+
+
+    ///
+    /// This function creates a new game with these rules and the given seed.
+    ///
+    /// Returns None if there is no piece generator with the stored name.
+    ///
+    pub fn create_game(&self, seed: u64) -> Option<Game> {
+        let piece_generator = generators::get_generator(&self.generator)?;
+        let mut game = Game::with_generator(Board::with_hidden_rows(self.rows, self.columns, self.hidden_rows), piece_generator, seed);
+
+        game.lock_delay = LockDelay::new(self.lock_delay_mode, self.lock_delay);
+        game.lock_delay.max_resets = self.max_resets;
+        game.gravity_curve = self.gravity_curve;
+        game.set_starting_level(self.starting_level);
+        game.set_preview_size(self.preview_size);
+
+        Some(game)
+    }
+}
+
+
+///
+/// TESTS BEGIN HERE
+///
+#[cfg(test)]
+mod tests {
+    use board::Board;
+    use game::Game;
+    use game::rules::Rules;
+    use game::lock_delay::{LockDelay, LockDelayMode};
+    use pieces::generators::nes_generator::NesGenerator;
+    use utility::gravity_curve::GravityCurve;
+
+    #[test]
+    fn rules_create_an_equal_game() {
+        let mut game = Game::with_generator(Board::with_hidden_rows(22, 8, 2), Box::new(NesGenerator::new()), 3);
+        game.lock_delay = LockDelay::new(LockDelayMode::Infinite, 0.25);
+        game.gravity_curve = GravityCurve::Nes;
+        game.set_starting_level(5);
+        game.set_preview_size(4);
+
+        let rules = Rules::from_game(&game);
+        let copy = rules.create_game(3).unwrap();
+        assert_eq!(Rules::from_game(&copy), rules);
+        assert_eq!(copy.level, 5);
+        assert_eq!(copy.preview_size, 4);
+        assert_eq!(copy.board, game.board);
+        assert_eq!(copy.spawn_position, game.spawn_position);
+
+        let mut unknown = rules.clone();
+        unknown.generator = "unknown".to_string();
+        assert!(unknown.create_game(3).is_none());
+    }
+}
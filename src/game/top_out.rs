@@ -0,0 +1,26 @@
+// Make this enum copyable, cloneable, printable, comparable and serializable.
+// Represents the guideline conditions that end a game.
+//
+// - BlockOut:      a new piece spawned overlapping a block
+// - LockOut:       a piece locked completely inside the hidden rows above the visible board
+// - GarbageOut:    garbage pushed a block or the actual piece above the top of the board
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub enum TopOut {
+    BlockOut,
+    LockOut,
+    GarbageOut,
+}
+
+
+impl TopOut {
+    ///
+    /// This function returns the name of the top out shown on the game over screens, e.g. "Block Out".
+    ///
+    pub fn name(&self) -> &'static str {
+        match self {
+            TopOut::BlockOut => "Block Out",
+            TopOut::LockOut => "Lock Out",
+            TopOut::GarbageOut => "Garbage Out",
+        }
+    }
+}
@@ -1,9 +1,14 @@
 // Imports.
-use event;
-use GUI;
-use Context;
-use GameResult;
-use MouseButton;
+use ggez::{Context, GameResult};
+use ggez::event::{self, Keycode, Mod, MouseButton};
+
+use game::top_out::TopOut;
+use gui::GUI;
+use high_scores::{HighScores, HighScoreEntry, HighScoreKey};
+
+
+// The longest name that can be entered for a high score.
+const MAX_NAME_LENGTH: usize = 12;
 
 ///
 /// This struct represents the game_over_menu.
@@ -12,24 +17,82 @@ use MouseButton;
 /// - play_again:       indicates if the "restart" button was pressed
 /// - game_rows:        completed_rows
 /// - game_points:      achieved_points
+/// - top_out:          the reason the game ended, None if it wasn't topped out
 /// - gui:              the information of the gui
+/// - high_scores:      the high score tables, loaded from the user's data directory
+/// - key:              the table the game competes in, None if no game was played
+/// - name:             the name entered so far, Some only while a qualifying human score waits for its name
 ///
 pub struct GameOverState {
     pub draw_once: bool,
     pub play_again: bool,
     pub game_rows: usize,
     pub game_points: usize,
-    pub gui: GUI
+    pub top_out: Option<TopOut>,
+    pub gui: GUI,
+    pub high_scores: HighScores,
+    pub key: Option<HighScoreKey>,
+    pub name: Option<String>,
 }
 
 impl GameOverState {
-    pub fn new(game_rows: usize, game_points: usize) -> GameOverState {
-        GameOverState{
+    pub fn new(game_rows: usize, game_points: usize, top_out: Option<TopOut>, key: Option<HighScoreKey>) -> GameOverState {
+        let high_scores = match HighScores::load(&HighScores::default_path()) {
+            Ok(high_scores) => high_scores,
+            Err(e) => {
+                println!("High Score Error, {} in /game_over_state.rs", e);
+                HighScores::new()
+            }
+        };
+
+        let mut game_over_state = GameOverState{
             draw_once: false,
             play_again: false,
             game_rows,
             game_points,
-            gui: GUI::new()
+            top_out,
+            gui: GUI::new(),
+            high_scores,
+            key,
+            name: None,
+        };
+
+        // The autoplayer can't enter a name, so its scores are stored right away. Humans are asked for their name.
+        let autoplay = match game_over_state.key {
+            Some(ref key) if game_over_state.high_scores.qualifies(key, game_points) => Some(key.autoplay),
+            _ => None,
+        };
+        match autoplay {
+            Some(true) => game_over_state.save_high_score("Autoplayer"),
+            Some(false) => game_over_state.name = Some(String::new()),
+            None => {}
+        }
+
+        game_over_state
+    }
+
+
+    ///
+    /// This function inserts the score with the given name into its table and saves the high scores.
+    ///
+    fn save_high_score(&mut self, name: &str) {
+        if let Some(ref key) = self.key {
+            self.high_scores.insert(key, HighScoreEntry::new(name, self.game_points, self.game_rows));
+            if let Err(e) = self.high_scores.save(&HighScores::default_path()) {
+                println!("High Score Error, {} in /game_over_state.rs", e);
+            }
+        }
+    }
+
+
+    ///
+    /// This function finishes the name entry, an empty name is stored as "Player".
+    ///
+    fn finish_name_entry(&mut self) {
+        if let Some(name) = self.name.take() {
+            let name = name.trim();
+            self.save_high_score(if name.is_empty() { "Player" } else { name });
+            self.draw_once = false;
         }
     }
 }
@@ -48,7 +111,7 @@ impl event::EventHandler for GameOverState {
     fn draw(&mut self, ctx: &mut Context) -> GameResult<()> {
         // Draws Game Over Screen only once.
         if !self.draw_once {
-            self.gui.draw_game_over(ctx, self.game_rows, self.game_points)?;
+            self.gui.draw_game_over(ctx, self.game_rows, self.game_points, self.top_out, self.name.as_deref())?;
             self.draw_once = true;
         }
 
@@ -56,9 +119,51 @@ impl event::EventHandler for GameOverState {
     }
 
 
+    ///
+    /// This function handles the typed text, while the name for a high score is entered.
+    ///
+    fn text_input_event(&mut self, _ctx: &mut Context, text: String) {
+        if let Some(ref mut name) = self.name {
+            for character in text.chars().filter(|character| !character.is_control()) {
+                if name.chars().count() < MAX_NAME_LENGTH {
+                    name.push(character);
+                }
+            }
+            self.draw_once = false;
+        }
+    }
+
+
+    ///
+    /// This function handles key_events.
+    /// Controls are:   Backspace           - to delete the last character of the name.
+    ///                 Return              - to store the high score with the entered name.
+    ///                 Escape              - to store the entered name and quit.
+    ///
+    fn key_down_event(&mut self, ctx: &mut Context, keycode: Keycode, _key_mods: Mod, _repeat: bool) {
+        match keycode {
+            Keycode::Backspace => {
+                if let Some(ref mut name) = self.name {
+                    name.pop();
+                    self.draw_once = false;
+                }
+            }
+            Keycode::Return | Keycode::KpEnter => self.finish_name_entry(),
+            Keycode::Escape => {
+                self.finish_name_entry();
+                if ctx.quit().is_err() {
+                    println!("Quit Error, EscapeKeyEvent in /game_over_state.rs");
+                }
+            }
+            _ => {}
+        }
+    }
+
+
     ///
     /// This function handles mouse_events.
     /// Used to select "Restart", to restart the game from the main menu again.
+    /// While a name is entered, "Restart" stores the high score first.
     ///
     fn mouse_button_down_event(&mut self, ctx: &mut Context, button: MouseButton, x: i32, y: i32) {
 
@@ -68,20 +173,18 @@ impl event::EventHandler for GameOverState {
         }
 
         // Button positions. ( Could be moved to the gui )
-        let columns = self.gui.columns;
-        let size = self.gui.block_size;
-
         let x = x as f32;
         let y = y as f32;
 
-        let width = (columns + 2.0) * size;
-        let height = 3.0 * size;
+        let width = self.gui.window_width();
+        let height = self.gui.screen_y(3.0);
         let pos_x = 0.0;
-        let pos_y = 11.0 * size;
+        let pos_y = self.gui.screen_y(11.0);
 
 
         // If the play button was clicked, set restart to true.
         if x >= pos_x && x <= pos_x + width && y >= pos_y && y <= pos_y + height {
+            self.finish_name_entry();
             self.play_again = true;
         } else {
             return;
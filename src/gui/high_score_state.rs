@@ -0,0 +1,119 @@
+// Imports.
+use ggez::{Context, GameResult};
+use ggez::event::{self, Keycode, Mod, MouseButton};
+
+use gui::GUI;
+use high_scores::HighScores;
+
+
+///
+/// This struct represents the High_score_state, where the high score tables are shown.
+///
+/// - draw_once:    draw the table just once, until another table is selected
+/// - high_scores:  all high score tables, loaded from the user's data directory
+/// - table:        the index of the shown table
+/// - gui:          the information of the gui
+///
+pub struct HighScoreState {
+    pub draw_once: bool,
+    pub high_scores: HighScores,
+    pub table: usize,
+    pub gui: GUI,
+}
+
+
+impl Default for HighScoreState {
+    fn default() -> HighScoreState {
+        HighScoreState::new()
+    }
+}
+
+
+impl HighScoreState {
+    pub fn new() -> HighScoreState {
+        let high_scores = match HighScores::load(&HighScores::default_path()) {
+            Ok(high_scores) => high_scores,
+            Err(e) => {
+                println!("High Score Error, {} in /high_score_state.rs", e);
+                HighScores::new()
+            }
+        };
+
+        HighScoreState {
+            draw_once: false,
+            high_scores,
+            table: 0,
+            gui: GUI::new(),
+        }
+    }
+
+
+    ///
+    /// This function selects the next table, "forwards" decides the direction. The selection wraps around.
+    ///
+    fn switch_table(&mut self, forwards: bool) {
+        let tables = self.high_scores.tables.len();
+        if tables == 0 {
+            return;
+        }
+
+        self.table = if forwards { (self.table + 1) % tables } else { (self.table + tables - 1) % tables };
+        self.draw_once = false;
+    }
+
+
+    ///
+    /// This function quits the context, so that main.rs goes back to the start screen.
+    ///
+    fn back(&self, ctx: &mut Context) {
+        if ctx.quit().is_err() {
+            println!("Quit Error, back() in /high_score_state.rs");
+        }
+    }
+}
+
+
+impl event::EventHandler for HighScoreState {
+    fn update(&mut self, _ctx: &mut Context) -> GameResult<()> {
+        /* do nothing */
+        Ok(())
+    }
+
+
+    ///
+    /// This function draws the selected high score table, but it draws it only once.
+    ///
+    fn draw(&mut self, ctx: &mut Context) -> GameResult<()> {
+        if !self.draw_once {
+            self.gui.draw_high_scores(ctx, self.high_scores.tables.get(self.table))?;
+            self.draw_once = true;
+        }
+
+        Ok(())
+    }
+
+
+    ///
+    /// This function handles key_events.
+    /// Controls are:   Left and Right      - to switch between the tables.
+    ///                 Escape              - to go back to the start screen.
+    ///
+    fn key_down_event(&mut self, ctx: &mut Context, keycode: Keycode, _key_mods: Mod, _repeat: bool) {
+        match keycode {
+            Keycode::Left => self.switch_table(false),
+            Keycode::Right => self.switch_table(true),
+            Keycode::Escape => self.back(ctx),
+            _ => {}
+        }
+    }
+
+
+    ///
+    /// A click anywhere goes back to the start screen as well.
+    ///
+    fn mouse_button_down_event(&mut self, ctx: &mut Context, button: MouseButton, _x: i32, _y: i32) {
+        if button == MouseButton::Left {
+            self.back(ctx);
+        }
+    }
+}
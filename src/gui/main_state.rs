@@ -1,18 +1,62 @@
 // Imports.
-use GUI;
-use Game;
-use Autoplayer;
-use Command;
-use MoveDirection;
-use GameResult;
-use Context;
-use Keycode;
-use Mod;
-use Board;
-
-use event;
+use ggez::{Context, GameResult};
+use ggez::event::{self, Keycode, Mod, MouseButton};
+use ggez::timer;
+
+use gui::GUI;
+use game::Game;
+use game::auto_repeat::{self, AutoRepeat};
+use game::lock_delay::LockDelay;
+use autoplayer::{self, Autoplayer};
+use board::Board;
+use replay::Replay;
+use replay::input::Input;
+use save_game::SaveGame;
+use settings::Settings;
+use settings::controls::Action;
 use utility;
-use timer;
+
+
+// Make this enum cloneable, copyable, printable and comparable.
+// Represents the options of the pause menu, from top to bottom.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PauseOption {
+    Resume,
+    Restart,
+    Settings,
+    Quit,
+}
+
+
+impl PauseOption {
+    // All options in the order they are drawn.
+    pub const ALL: [PauseOption; 4] = [PauseOption::Resume, PauseOption::Restart, PauseOption::Settings, PauseOption::Quit];
+
+
+    ///
+    /// This function returns the text drawn for the option.
+    ///
+    pub fn label(&self) -> &'static str {
+        match self {
+            PauseOption::Resume => "Resume",
+            PauseOption::Restart => "Restart",
+            PauseOption::Settings => "Settings",
+            PauseOption::Quit => "Quit",
+        }
+    }
+
+
+    ///
+    /// This function returns the option above ({up} = true) or below this one, the selection wraps around.
+    ///
+    pub fn neighbour(&self, up: bool) -> PauseOption {
+        let options = PauseOption::ALL.len();
+        let index = PauseOption::ALL.iter().position(|option| option == self).unwrap();
+        let index = if up { (index + options - 1) % options } else { (index + 1) % options };
+
+        PauseOption::ALL[index]
+    }
+}
 
 
 ///
@@ -20,57 +64,188 @@ use timer;
 ///
 /// - gui:                  the information of the GUI
 /// - game:                 the game
-/// - time_since_start_old: used to compute the automatic move-down correctly
+/// - time_since_step:      the time in seconds since the last automatic move-down, it doesn't run while paused
 /// - autoplay:             indicates if the autoplayer is running
 /// - is_game_over:         indicates if the game is over
 /// - auto_player:          the actual auto_player
+/// - replay:               the recording of every input applied to the game
+/// - game_time:            the time in seconds since the game started, used to timestamp the inputs
+/// - saved:                indicates if the game was saved to be continued later ("Save & Quit")
+/// - paused:               indicates if the pause menu is open, the game is frozen and hidden while paused
+/// - pause_selection:      the selected option of the pause menu
+/// - restart:              indicates if "Restart" was selected, main.rs starts a new game then
+/// - open_settings:        indicates if "Settings" was selected, main.rs opens the settings and runs the paused game again
+/// - auto_repeat:          repeats the held movement keys with the DAS and ARR of the settings
 ///
 pub struct MainState {
     pub gui: GUI,
     pub game: Game,
-    pub time_since_start_old: f64,
+    pub time_since_step: f64,
     pub autoplay: bool,
     pub is_game_over: bool,
     pub auto_player: Autoplayer,
+    pub replay: Replay,
+    pub game_time: f64,
+    pub saved: bool,
+    pub paused: bool,
+    pub pause_selection: PauseOption,
+    pub restart: bool,
+    pub open_settings: bool,
+    pub auto_repeat: AutoRepeat,
 }
 
 
 impl MainState {
     pub fn new(autoplay: bool) -> MainState {
-        let gui = GUI::new();
-        let game = Game::new(Board::new(gui.rows as usize, gui.columns as usize));
-        let auto_player = Autoplayer::new();
+        MainState::with_settings(autoplay, Settings::load_or_default())
+    }
+
+
+    ///
+    /// This function creates the main state for a new game with the given settings instead of the ones of the settings file,
+    /// e.g. with the board size given on the command line.
+    ///
+    pub fn with_settings(autoplay: bool, settings: Settings) -> MainState {
+        let rows = settings.rows;
+        let columns = settings.columns;
+        let gui = GUI::with_board_size(settings, rows, columns);
+        let mut game = Game::new(Board::with_hidden_rows(gui.rows as usize, gui.columns as usize, gui.settings.hidden_rows));
+        game.gravity_curve = gui.settings.gravity_curve;
+        game.set_starting_level(gui.settings.starting_level);
+        game.set_preview_size(gui.settings.preview_size);
+
+        if autoplay {
+            game.lock_delay = LockDelay::autoplayer();
+        }
+        let auto_player = Autoplayer::with_evaluator(gui.settings.evaluator(), autoplayer::DEFAULT_LOOKAHEAD);
+        let replay = Replay::new(&game, autoplay);
+        let auto_repeat = AutoRepeat::new(gui.settings.handling);
         MainState {
             gui,
             game,
-            time_since_start_old: 0.0,
+            time_since_step: 0.0,
             autoplay,
             is_game_over: false,
             auto_player,
+            replay,
+            game_time: 0.0,
+            saved: false,
+            paused: false,
+            pause_selection: PauseOption::Resume,
+            restart: false,
+            open_settings: false,
+            auto_repeat,
         }
 
     }
 
+
     ///
-    /// This function translates the autoplayer move command to a game step and performs the move.
+    /// This function creates the main state for a saved game, which continues exactly where it was left.
     ///
-    pub fn auto_player_move(&mut self) {
+    /// The gravity timer starts from zero, the time spent in the menu doesn't let the piece fall.
+    ///
+    pub fn from_save(save_game: SaveGame) -> MainState {
+        let mut game = save_game.game;
+        // The saved game keeps its preview size, the window is made wide enough for it.
+        let mut settings = Settings::load_or_default();
+        let mut auto_player = Autoplayer::with_evaluator(settings.evaluator(), autoplayer::DEFAULT_LOOKAHEAD);
+        settings.preview_size = game.preview_size;
+        let gui = GUI::with_board_size(settings, game.board.visible_rows(), game.board.columns);
+        let auto_repeat = AutoRepeat::new(gui.settings.handling);
+        if save_game.autoplay {
+            auto_player.compute_move(&mut game);
+        }
 
-        match self.auto_player.perform_move(&mut self.game) {
-            Command::Down => {
-                if self.game.step(MoveDirection::Down) { self.auto_player.compute_move(&mut self.game); }
-            }
-            Command::Left => {
-                if self.game.step(MoveDirection::Left) { self.auto_player.compute_move(&mut self.game); }
-            }
-            Command::Right => {
-                if self.game.step(MoveDirection::Right) { self.auto_player.compute_move(&mut self.game); }
-            }
-            Command::RotateClockWise => {
-                self.game.rotate_piece_clockwise();
+        MainState {
+            gui,
+            game,
+            time_since_step: 0.0,
+            autoplay: save_game.autoplay,
+            is_game_over: false,
+            auto_player,
+            replay: save_game.replay,
+            game_time: save_game.game_time,
+            saved: false,
+            paused: false,
+            pause_selection: PauseOption::Resume,
+            restart: false,
+            open_settings: false,
+            auto_repeat,
+        }
+    }
+
+
+    ///
+    /// This function saves the game to SaveGame::default_path() and quits, so that it can be continued later.
+    ///
+    pub fn save_and_quit(&mut self, ctx: &mut Context) {
+        let save_game = SaveGame::new(&self.game, &self.replay, self.autoplay, self.game_time);
+        if let Err(e) = save_game.save(&SaveGame::default_path()) {
+            println!("Save Error, {} in /main_state.rs", e);
+            return;
+        }
+
+        self.saved = true;
+        if ctx.quit().is_err() {
+            println!("Quit Error, SaveKeyEvent in /main_state.rs");
+        }
+    }
+
+    ///
+    /// This function opens the pause menu with "Resume" selected.
+    ///
+    pub fn pause(&mut self) {
+        if !self.game.is_game_over() {
+            self.paused = true;
+            self.pause_selection = PauseOption::Resume;
+
+            // Keys released while paused would keep repeating, so every key has to be pressed again.
+            self.auto_repeat.release_all();
+        }
+    }
+
+
+    ///
+    /// This function performs the given option of the pause menu.
+    ///
+    /// "Restart", "Settings" and "Quit" leave the events_loop, main.rs decides what happens next.
+    ///
+    pub fn select(&mut self, ctx: &mut Context, option: PauseOption) {
+        match option {
+            PauseOption::Resume => {
+                self.paused = false;
+                return;
             }
+            PauseOption::Restart => self.restart = true,
+            PauseOption::Settings => self.open_settings = true,
+            PauseOption::Quit => {}
+        }
+
+        if ctx.quit().is_err() {
+            println!("Quit Error, PauseMenu in /main_state.rs");
         }
     }
+
+
+    ///
+    /// This function translates the autoplayer move command to a game step and performs the move.
+    ///
+    pub fn auto_player_move(&mut self) {
+        let command = self.auto_player.play_move(&mut self.game);
+        self.replay.record(self.game_time, Input::from_command(&command));
+    }
+
+
+    ///
+    /// This function records the input in the replay and applies it to the game.
+    ///
+    /// It returns {true} if a new piece was added and {false} otherwise.
+    ///
+    pub fn apply(&mut self, input: Input) -> bool {
+        self.replay.record(self.game_time, input);
+        input.apply(&mut self.game)
+    }
 }
 
 ///
@@ -81,27 +256,54 @@ impl event::EventHandler for MainState {
     ///
     /// Here is where the game logic takes place.
     ///
+    /// Nothing happens while paused. The timers only add up the time of unpaused frames, so nothing jumps on resume.
+    ///
     fn update(&mut self, ctx: &mut Context) -> GameResult<()> {
+        if self.paused {
+            return Ok(());
+        }
+
         let duration = utility::duration_for_level(&self.game);
-        let time_since_start_new = timer::duration_to_f64(timer::get_time_since_start(ctx));
+        let delta = timer::duration_to_f64(timer::get_delta(ctx));
+        self.time_since_step += delta;
+        self.game_time += delta;
 
         // Autoplayer is active.
         if self.autoplay {
-            if time_since_start_new - self.time_since_start_old >= duration {
-                if self.game.step(MoveDirection::Down) {
+            if self.time_since_step >= duration {
+                if self.apply(Input::Gravity) {
                     self.auto_player.compute_move(&mut self.game);
                     self.gui.draw_content(&mut self.game, ctx)?;
                 }
-                self.time_since_start_old = time_since_start_new;
+                self.time_since_step = 0.0;
             } else {
                 self.auto_player_move();
                 self.gui.draw_content(&mut self.game, ctx)?;
             }
             // Human player is active.
         } else {
-            if time_since_start_new - self.time_since_start_old >= duration {
-                self.game.step(MoveDirection::Down);
-                self.time_since_start_old = time_since_start_new;
+            if self.time_since_step >= duration {
+                // Fast gravity curves move the piece down several rows per update.
+                let rows = (self.time_since_step / duration) as usize;
+                for _ in 0..rows.min(self.game.board.rows) {
+                    if self.apply(Input::Gravity) {
+                        break;
+                    }
+                }
+                self.time_since_step = 0.0;
+            }
+
+            // Held movement keys repeat with the DAS and ARR, they stop at the wall and on the ground.
+            for input in self.auto_repeat.update(delta, duration, self.game.board.columns.max(self.game.board.rows)) {
+                if auto_repeat::can_apply(&mut self.game, input) {
+                    self.apply(input);
+                }
+            }
+
+            // Lock the piece once it rested on the ground for long enough.
+            // The replay doesn't know about the time, so it records the moment the piece locked.
+            if self.game.update_lock_delay(delta) {
+                self.replay.record(self.game_time, Input::Lock);
             }
         }
 
@@ -115,62 +317,86 @@ impl event::EventHandler for MainState {
     }
 
     ///
-    /// This function is used to draw the content onto the screen.
+    /// This function is used to draw the content onto the screen, or the pause menu while paused.
     ///
     fn draw(&mut self, ctx: &mut Context) -> GameResult<()> {
-        self.gui.draw_content(&mut self.game, ctx)?;
+        if self.paused {
+            self.gui.draw_pause_menu(ctx, &self.game, self.pause_selection)?;
+        } else {
+            self.gui.draw_content(&mut self.game, ctx)?;
+        }
 
         Ok(())
     }
 
     ///
     /// This function handles key_events.
+    /// The keys can be rebound in the settings, by default they are:
     /// Controls are:   Left, Right, Down   - to move the piece in the given direction.
+    ///                 Space               - to hard drop the piece.
+    ///                 C                   - to hold the piece.
     ///                 Y and X             - to rotate the piece clock- and counter- clockwise.
-    ///                 Escape              - to quit the game early.
+    ///                 S                   - to save the game and quit, it can be continued from the start screen.
+    ///                 Escape and P        - to pause the game.
+    /// In the pause menu:
+    ///                 Up and Down         - to select an option.
+    ///                 Return              - to perform the selected option.
+    ///                 Escape and Pause    - to resume the game.
     ///
-    fn key_down_event(&mut self, ctx: &mut Context, keycode: Keycode, _key_mods: Mod, _repeat: bool) {
+    fn key_down_event(&mut self, ctx: &mut Context, keycode: Keycode, _key_mods: Mod, repeat: bool) {
         if self.game.is_game_over() {
             return;
         }
 
-        // Match on the keycode of the Key that was pressed.
-        match keycode {
-            Keycode::Left => {
-                if !self.autoplay {
-                    self.game.step(MoveDirection::Left);
+        if self.paused {
+            match keycode {
+                Keycode::Up => self.pause_selection = self.pause_selection.neighbour(true),
+                Keycode::Down => self.pause_selection = self.pause_selection.neighbour(false),
+                Keycode::Return | Keycode::KpEnter => {
+                    let option = self.pause_selection;
+                    self.select(ctx, option);
                 }
-            }
-            Keycode::Right => {
-                if !self.autoplay {
-                    self.game.step(MoveDirection::Right);
-                }
-            }
-            Keycode::Down => {
-                if !self.autoplay {
-                    self.game.step(MoveDirection::Down);
-                }
-            }
-            Keycode::Y => {
-                if !self.autoplay {
-                    self.game.rotate_piece_clockwise();
+                Keycode::Escape => self.paused = false,
+                _ => {
+                    if self.gui.settings.controls.action(&keycode.name()) == Some(Action::Pause) {
+                        self.paused = false;
+                    }
                 }
             }
-            Keycode::X => {
-                if !self.autoplay {
-                    self.game.rotate_piece_counter_clockwise();
-                }
+            return;
+        }
+
+        // Held keys are repeated by the auto_repeat, not by the operating system.
+        if repeat {
+            return;
+        }
+
+        // Match on the action the pressed key is bound to in the settings.
+        let input = match self.gui.settings.controls.action(&keycode.name()) {
+            Some(Action::MoveLeft) => Input::Left,
+            Some(Action::MoveRight) => Input::Right,
+            Some(Action::SoftDrop) => Input::SoftDrop,
+            Some(Action::HardDrop) => Input::HardDrop,
+            Some(Action::Hold) => Input::Hold,
+            Some(Action::RotateClockwise) => Input::RotateClockwise,
+            Some(Action::RotateCounterClockwise) => Input::RotateCounterClockwise,
+            Some(Action::SaveAndQuit) => {
+                self.save_and_quit(ctx);
+                return;
             }
-            Keycode::Escape => {
-                match ctx.quit() {
-                    Err(_e) => {
-                        println!("Quit Error, EscapeKeyEvent in /main.rs");
-                    }
-                    _ => {}
-                }
+            Some(Action::Pause) => {
+                self.pause();
+                return;
             }
-            _ => { return; }
+            None => { return; }
+        };
+
+        // The autoplayer's game can't be controlled.
+        if self.autoplay {
+            return;
         }
+        self.apply(input);
+        self.auto_repeat.press(input);
 
         // Draw the content after each key_event. The update function felt too slow.
         match self.gui.draw_content(&mut self.game, ctx) {
@@ -180,4 +406,49 @@ impl event::EventHandler for MainState {
             _ => {}
         }
     }
+
+
+    ///
+    /// This function handles key_up_events, released movement keys stop repeating.
+    ///
+    fn key_up_event(&mut self, _ctx: &mut Context, keycode: Keycode, _key_mods: Mod, _repeat: bool) {
+        let input = match self.gui.settings.controls.action(&keycode.name()) {
+            Some(Action::MoveLeft) => Input::Left,
+            Some(Action::MoveRight) => Input::Right,
+            Some(Action::SoftDrop) => Input::SoftDrop,
+            _ => return,
+        };
+        self.auto_repeat.release(input);
+    }
+
+
+    ///
+    /// This function handles mouse_events.
+    /// Used to select an option of the pause menu by clicking on it.
+    ///
+    fn mouse_button_down_event(&mut self, ctx: &mut Context, button: MouseButton, x: i32, y: i32) {
+        if !self.paused || button != MouseButton::Left {
+            return;
+        }
+
+        let x = x as f32;
+        let y = y as f32;
+        for (index, option) in PauseOption::ALL.iter().enumerate() {
+            let rect = self.gui.pause_option_button(index);
+            if x >= rect.x && x <= rect.x + rect.w && y >= rect.y && y <= rect.y + rect.h {
+                self.select(ctx, *option);
+                return;
+            }
+        }
+    }
+
+
+    ///
+    /// The game pauses itself as soon as the window loses the focus.
+    ///
+    fn focus_event(&mut self, _ctx: &mut Context, gained: bool) {
+        if !gained && !self.paused {
+            self.pause();
+        }
+    }
 }
\ No newline at end of file
@@ -3,10 +3,16 @@
 /// Start_state:        representing the game menu
 /// Main_state:         representing the game itself
 /// Game_over_state:    representing the game_over menu
+/// Replay_state:       representing the playback of a recorded game
+/// High_score_state:   representing the high score tables
+/// Settings_state:     representing the settings, reachable from the pause menu
 ///
 pub mod start_state;
 pub mod main_state;
 pub mod game_over_state;
+pub mod replay_state;
+pub mod high_score_state;
+pub mod settings_state;
 
 // Imports
 use std::env;
@@ -17,11 +23,34 @@ use ggez::conf::{WindowMode, WindowSetup};
 use ggez::graphics::{self, WHITE, BLACK, Rect, Color, DrawParam, DrawMode, Font, Text, Image};
 
 use game::Game;
-use board::Board;
+use game::top_out::TopOut;
+use gui::main_state::PauseOption;
+use high_scores::HighScoreTable;
+use settings::Settings;
+use settings::controls::Action;
 use pieces::piece::Piece;
 use pieces::piece_type::PieceType;
 
-use nalgebra::Point2;
+use ggez::nalgebra::Point2;
+
+
+// The amount of entries on the settings screen in front of the controls.
+pub const SETTINGS_VALUES: usize = 11;
+
+
+// The width of the preview column to the right of the board, in blocks.
+const PREVIEW_COLUMNS: f32 = 4.0;
+
+// The height in blocks the start and game over images are laid out for, the height of the window around 20 rows.
+const LAYOUT_ROWS: f32 = 30.0;
+
+
+///
+/// This function returns the width of the window in blocks, the board with its border and the preview column if there is a preview.
+///
+fn window_columns(columns: f32, preview_size: usize) -> f32 {
+    if preview_size > 0 { columns + 2.0 + PREVIEW_COLUMNS } else { columns + 2.0 }
+}
 
 
 pub struct GUI {
@@ -31,23 +60,46 @@ pub struct GUI {
     pub rows: f32,
     pub rows_text_pos: Point2<f32>,
     pub points_text_pos: Point2<f32>,
-    pub next_piece_pos: Point2<f32>,
+    pub hold_piece_pos: Point2<f32>,
     pub standings_rows_pos: Point2<f32>,
     pub standings_points_pos: Point2<f32>,
+    pub settings: Settings,
+}
+
+
+impl Default for GUI {
+    fn default() -> GUI {
+        GUI::new()
+    }
 }
 
 
 impl GUI {
     // DEFAULT PARAMETERS FOR THE GUI
-    // EXCHANGEABLE
+    // The settings are loaded from the settings file, the board size is taken from them.
     pub fn new() -> GUI {
+        let settings = Settings::load_or_default();
+        let rows = settings.rows;
+        let columns = settings.columns;
+
+        GUI::with_board_size(settings, rows, columns)
+    }
+
+
+    ///
+    /// This function creates the GUI for a board of the given size, e.g. for a saved game of another size.
+    /// "board_rows" are the visible rows, the window and every position on it are derived from them and the columns.
+    ///
+    pub fn with_board_size(settings: Settings, board_rows: usize, board_columns: usize) -> GUI {
         let resources_path = format!("{}/resources", env::current_dir().unwrap().to_str().unwrap());
         let block_size: f32 = 25.0;
-        let columns: f32 = 11.0;
-        let rows: f32 = 20.0;
-        let rows_text_pos = Point2::new(((columns + 2.0) * block_size) * 0.6, block_size * 16.5);
-        let points_text_pos = Point2::new(((columns + 2.0) * block_size) * 0.6, block_size * 19.5);
-        let next_piece_pos = Point2::new(columns - 3.0, rows + 3.0);
+        let columns: f32 = board_columns as f32;
+        let rows: f32 = board_rows as f32;
+        let width = window_columns(columns, settings.preview_size) * block_size;
+        let height = (rows + 10.0) * block_size;
+        let rows_text_pos = Point2::new(width * 0.6, height * 16.5 / LAYOUT_ROWS);
+        let points_text_pos = Point2::new(width * 0.6, height * 19.5 / LAYOUT_ROWS);
+        let hold_piece_pos = Point2::new(columns / 2.0 - 0.5, rows + 3.0);
         let standings_rows_pos = Point2::new(block_size, (rows + 4.0) * block_size);
         let standings_points_pos = Point2::new(block_size, (rows + 6.0) * block_size);
 
@@ -58,9 +110,10 @@ impl GUI {
             rows,
             rows_text_pos,
             points_text_pos,
-            next_piece_pos,
+            hold_piece_pos,
             standings_rows_pos,
-            standings_points_pos
+            standings_points_pos,
+            settings,
         }
     }
 
@@ -75,11 +128,49 @@ impl GUI {
         ContextBuilder::new("Tetris", "Brendon.Sutaj")
             .add_resource_path(Path::new(&self.resources_path))
             .window_setup(WindowSetup::default().title("Tetris").icon("/icon.png"))
-            .window_mode(WindowMode::default().dimensions((self.columns + 2.0) as u32 * self.block_size as u32, (self.rows + 10.0) as u32 * self.block_size as u32))
+            .window_mode(WindowMode::default().dimensions(self.window_width() as u32, self.window_height() as u32))
             .build().expect("Error in /gui/mod.rs, building the context failed.")
     }
 
 
+    ///
+    /// This function returns the width of the window in pixels.
+    ///
+    pub fn window_width(&self) -> f32 {
+        window_columns(self.columns, self.settings.preview_size) * self.block_size
+    }
+
+
+    ///
+    /// This function returns the height of the window in pixels, the visible rows and the standings below them.
+    ///
+    pub fn window_height(&self) -> f32 {
+        (self.rows + 10.0) * self.block_size
+    }
+
+
+    ///
+    /// This function converts a height on the start and game over images, given in blocks of the default window, to pixels.
+    ///
+    /// The images are stretched to the window, so everything on them moves with the amount of rows.
+    ///
+    pub fn screen_y(&self, blocks: f32) -> f32 {
+        blocks * self.window_height() / LAYOUT_ROWS
+    }
+
+
+    ///
+    /// This function resizes the window to the board size of this GUI, the board size can change in the settings.
+    ///
+    pub fn resize_window(&self, ctx: &mut Context) -> GameResult<()> {
+        let width = self.window_width();
+        let height = self.window_height();
+
+        graphics::set_resolution(ctx, width as u32, height as u32)?;
+        graphics::set_screen_coordinates(ctx, Rect::new(0.0, 0.0, width, height))
+    }
+
+
     ///
     /// This function is used to draw the game contents onto the context.
     ///
@@ -87,17 +178,58 @@ impl GUI {
     ///     (1) Clear the Screen by graphics::clear(ctx);
     ///     (2) Now draw the board layout and the black background.
     ///     (3) Draw the standings (completed rows and achieved points).
-    ///     (4) Draw the actual board and all the pieces on it.
-    ///     (5) Draw the next_piece.
+    ///     (4) Draw the actual board, the ghost piece and all the pieces on it.
+    ///     (5) Draw the preview and the held_piece.
     ///     (6) Present everything by graphics::present(ctx); and ggez::timer::yield_now();
     ///
     pub fn draw_content(&self, game: &mut Game, ctx: &mut Context) -> GameResult<()> {
         graphics::clear(ctx);
 
+        self.draw_game(ctx, game)?;
+
+        graphics::present(ctx);
+        ggez::timer::yield_now();
+
+        Ok(())
+    }
+
+
+    ///
+    /// This function is used to draw a replayed game and the playback status below the standings.
+    ///
+    pub fn draw_replay_content(&self, game: &Game, ctx: &mut Context, status: &str) -> GameResult<()> {
+        graphics::clear(ctx);
+
+        self.draw_game(ctx, game)?;
+        self.draw_text(ctx, status, WHITE, Point2::new(self.block_size, (self.rows + 9.0) * self.block_size), self.block_size * 0.6)?;
+
+        graphics::present(ctx);
+        ggez::timer::yield_now();
+
+        Ok(())
+    }
+
+
+    ///
+    /// This function is used to draw the pause menu instead of the game.
+    ///
+    /// The board stays empty and the pieces are hidden, so that pausing can't be used to plan the next moves.
+    /// The selected option is drawn in orange, the others in white.
+    ///
+    pub fn draw_pause_menu(&self, ctx: &mut Context, game: &Game, selected: PauseOption) -> GameResult<()> {
+        graphics::clear(ctx);
+
+        let size = self.block_size;
         self.draw_layout(ctx)?;
-        self.draw_standings(ctx, &game)?;
-        self.draw_board(ctx, &game.board)?;
-        self.draw_next_piece(ctx, &game.next_piece)?;
+        self.draw_standings(ctx, game)?;
+        self.draw_text(ctx, "Paused", BLACK, Point2::new((self.columns / 2.0 - 1.0) * size, 4.0 * size), size * 1.5)?;
+
+        for (index, option) in PauseOption::ALL.iter().enumerate() {
+            let button = self.pause_option_button(index);
+            let color = if *option == selected { Color::from_rgb(255, 153, 51) } else { WHITE };
+            self.rectangle(ctx, button, Color::from_rgb(64, 64, 64))?;
+            self.draw_text(ctx, option.label(), color, Point2::new(button.x + size * 0.5, button.y + size * 0.25), size)?;
+        }
 
         graphics::present(ctx);
         ggez::timer::yield_now();
@@ -106,6 +238,30 @@ impl GUI {
     }
 
 
+    ///
+    /// This function returns the area of the pause menu option with the given index, they are stacked on the board.
+    ///
+    pub fn pause_option_button(&self, index: usize) -> Rect {
+        let size = self.block_size;
+
+        Rect::new(2.0 * size, (7.0 + 3.0 * index as f32) * size, (self.columns - 2.0) * size, 2.0 * size)
+    }
+
+
+    ///
+    /// This function draws the layout, the standings, the board, the preview and the held_piece.
+    ///
+    fn draw_game(&self, ctx: &mut Context, game: &Game) -> GameResult<()> {
+        self.draw_layout(ctx)?;
+        self.draw_standings(ctx, game)?;
+        self.draw_board(ctx, game)?;
+        self.draw_preview(ctx, game)?;
+        self.draw_hold_piece(ctx, &game.held_piece)?;
+
+        Ok(())
+    }
+
+
     ///
     /// This function draws the layout of the board onto the screen.
     ///
@@ -115,7 +271,7 @@ impl GUI {
         let size = self.block_size;
 
         // Drawing a black background calling the rectangle function.
-        let background = Rect::new(0.0, 0.0, size * (columns + 2.0), size * (rows + 10.0));
+        let background = Rect::new(0.0, 0.0, self.window_width(), size * (rows + 10.0));
         self.rectangle(ctx, background, BLACK)?;
 
         // Drawing the grey board background.
@@ -137,12 +293,28 @@ impl GUI {
     ///
     /// This function is used to draw the board and all the titles onto the screen.
     ///
-    fn draw_board(&self, ctx: &mut Context, board: &Board) -> GameResult<()> {
-        for x in 0..board.rows {
+    /// The ghost piece, where the actual piece would land, is drawn as a translucent outline.
+    /// Only the visible rows are drawn, the hidden rows above them stay off the screen.
+    ///
+    #[allow(clippy::needless_range_loop)]
+    fn draw_board(&self, ctx: &mut Context, game: &Game) -> GameResult<()> {
+        let board = &game.board;
+        let hidden_rows = board.hidden_rows;
+
+        if game.actual_piece.piece_type != PieceType::None {
+            let ghost = game.ghost_position();
+            for tile in game.actual_piece.get_tiles(ghost.x_coordinate, ghost.y_coordinate) {
+                if tile.x_coordinate >= hidden_rows {
+                    self.draw_ghost_tile(ctx, (tile.x_coordinate - hidden_rows) as f32, tile.y_coordinate as f32, &game.actual_piece.piece_type)?;
+                }
+            }
+        }
+
+        for x in hidden_rows..board.rows {
             for y in 0..board.columns {
                 // Draw every tile of the board.
                 if board.board[x][y] != PieceType::None {
-                    self.draw_board_tile(ctx, x as f32, y as f32, &board.board[x][y])?;
+                    self.draw_board_tile(ctx, (x - hidden_rows) as f32, y as f32, &board.board[x][y])?;
                 }
             }
         }
@@ -152,7 +324,8 @@ impl GUI {
 
 
     ///
-    /// This function draws the the standings completed_rows / achieved_points onto the screen.
+    /// This function draws the the standings completed_rows / level / achieved_points onto the screen.
+    /// Below them the name of the last clear like "T-Spin Double" is shown.
     ///
     fn draw_standings(&self, ctx: &mut Context, game: &Game) -> GameResult<()> {
         let size = self.block_size;
@@ -162,27 +335,70 @@ impl GUI {
         // Draw it onto the screen in the same color as the next_piece.
         let color = self.get_color_for_piece_type(&game.next_piece.piece_type);
         let row_text = format!("Rows: {}", game.rows);
+        let level_text = format!("Level: {}", game.level);
         let points_text = format!("Points: {}", game.points);
 
         // Draw the Text to the screen.
         self.draw_text(ctx, &row_text, color, Point2::new(size, (rows + 4.0) * size), size * 0.6)?;
+        self.draw_text(ctx, &level_text, color, Point2::new(size, (rows + 5.0) * size), size * 0.6)?;
         self.draw_text(ctx, &points_text, color, Point2::new(size, (rows + 6.0) * size), size * 0.6)?;
 
+        // Draw the name of the last clear, if there is something to show.
+        if let Some(ref clear_event) = game.last_clear_event {
+            let clear_text = clear_event.name();
+            if !clear_text.is_empty() {
+                self.draw_text(ctx, &clear_text, WHITE, Point2::new(size, (rows + 8.0) * size), size * 0.6)?;
+            }
+        }
+
+        Ok(())
+    }
+
+
+    ///
+    /// This function draws the preview, the next pieces stacked in a column to the right of the board.
+    ///
+    /// Every piece takes the rows it needs and one row of space, pieces that don't fit into the window are left out.
+    ///
+    fn draw_preview(&self, ctx: &mut Context, game: &Game) -> GameResult<()> {
+        let x = self.columns + 1.0;
+        let mut y = 1.0;
+
+        for (index, piece) in game.preview(game.preview_size).into_iter().enumerate() {
+            let height = piece.body.rows as f32;
+            if y + height > self.rows + 10.0 {
+                break;
+            }
+
+            let label = if index == 0 { "Next:" } else { "" };
+            self.draw_piece_box(ctx, piece, Point2::new(x, y), label)?;
+            y += height + 1.0;
+        }
+
         Ok(())
     }
 
 
     ///
-    /// This function draws the next piece onto the screen.
+    /// This function draws the held piece onto the screen.
     ///
-    fn draw_next_piece(&self, ctx: &mut Context, piece: &Piece) -> GameResult<()> {
+    fn draw_hold_piece(&self, ctx: &mut Context, piece: &Piece) -> GameResult<()> {
+        self.draw_piece_box(ctx, piece, self.hold_piece_pos, "Hold:")
+    }
+
+
+    ///
+    /// This function draws the given piece with the given label at the given position onto the screen.
+    ///
+    #[allow(clippy::needless_range_loop)]
+    fn draw_piece_box(&self, ctx: &mut Context, piece: &Piece, piece_pos: Point2<f32>, label: &str) -> GameResult<()> {
         // If the piece_type is NONE don't draw anything.
         if piece.piece_type == PieceType::None {
             return Ok(());
         }
 
-        let x = self.next_piece_pos.coords[0];
-        let y = self.next_piece_pos.coords[1];
+        let x = piece_pos.coords[0];
+        let y = piece_pos.coords[1];
         let size = self.block_size;
 
         // Draw every single piece_tile.
@@ -194,10 +410,12 @@ impl GUI {
             }
         }
 
-        // Draw "Next Piece" Text.
-        let position = Point2::new(x * size, (y - 1.0) * size);
-        let color = self.get_color_for_piece_type(&piece.piece_type);
-        self.draw_text(ctx, "Next Piece:", color, position, size * 0.6)?;
+        // Draw the label Text, pieces further down the preview have none.
+        if !label.is_empty() {
+            let position = Point2::new(x * size, (y - 1.0) * size);
+            let color = self.get_color_for_piece_type(&piece.piece_type);
+            self.draw_text(ctx, label, color, position, size * 0.6)?;
+        }
 
         // Draw a circle where the rotation point of the piece is located.
         self.draw_circle(ctx, y + piece.point.x_coordinate as f32, x + piece.point.y_coordinate as f32)?;
@@ -256,6 +474,25 @@ impl GUI {
     }
 
 
+    ///
+    /// This function draws the translucent outline of a ghost tile onto the screen.
+    ///
+    fn draw_ghost_tile(&self, ctx: &mut Context, x: f32, y: f32, piece_type: &PieceType) -> GameResult<()> {
+        let size = self.block_size;
+
+        // Get the right color for the given piece_type and make it translucent.
+        let mut color = self.get_color_for_piece_type(piece_type);
+        color.a = 0.5;
+
+        // Set the right tile_coordinates and draw the outline onto the screen.
+        let tile_coordinates = Rect::new(2.0 + (y + 1.0) * size, 2.0 + (x + 1.0) * size, size - 3.0, size - 3.0);
+        graphics::set_color(ctx, color)?;
+        graphics::rectangle(ctx, DrawMode::Line(2.0), tile_coordinates)?;
+
+        Ok(())
+    }
+
+
     ///
     /// This function draws the text onto the screen.
     ///
@@ -284,6 +521,7 @@ impl GUI {
             PieceType::S => Color::from_rgb(128, 255, 0),           // green
             PieceType::T => Color::from_rgb(128, 0, 128),           // purple
             PieceType::Z => Color::from_rgb(255, 0, 0),             // red
+            PieceType::Garbage => Color::from_rgb(96, 96, 96),      // dark-grey
             PieceType::None => WHITE,                                         // white
         }
     }
@@ -292,10 +530,12 @@ impl GUI {
     ///
     /// This function draws the game_over_screen with the achieved points and the completed rows.
     ///
-    pub fn draw_game_over(&self, ctx: &mut Context, game_rows: usize, game_points: usize) -> GameResult<()> {
+    /// The reason the game ended, e.g. "Lock Out", is drawn below the standings.
+    /// If the score made it into the high scores, "name" is the name entered so far and a prompt is drawn below.
+    ///
+    pub fn draw_game_over(&self, ctx: &mut Context, game_rows: usize, game_points: usize, top_out: Option<TopOut>, name: Option<&str>) -> GameResult<()> {
         let size = self.block_size;
         let rows = self.rows;
-        let columns = self.columns;
 
         // clear the stuff before
         graphics::clear(ctx);
@@ -303,8 +543,8 @@ impl GUI {
         // Draw Game Over Screen
         let game_over_image = Image::new(ctx, "/game_over_screen.png")?;
 
-        // Scaling the Image to (width // height) = (size * (columns + 2.0) // size * (rows + 10.0))
-        let scale_x = size * (columns + 2.0) / game_over_image.width() as f32;
+        // Scaling the Image to (width // height) = (window_width // size * (rows + 10.0))
+        let scale_x = self.window_width() / game_over_image.width() as f32;
         let scale_y = size * (rows + 10.0) / game_over_image.height() as f32;
 
         let draw_param = DrawParam{
@@ -332,6 +572,15 @@ impl GUI {
         self.draw_text(ctx, &rows_text, color, rows_text_pos, size * 1.0)?;
         self.draw_text(ctx, &points_text, color, points_text_pos, size * 1.0)?;
 
+        if let Some(top_out) = top_out {
+            self.draw_text(ctx, top_out.name(), WHITE, Point2::new(size, (rows + 2.5) * size), size * 0.7)?;
+        }
+
+        // Draw the name entry below the standings.
+        if let Some(name) = name {
+            self.draw_text(ctx, "New High Score! Enter your name:", color, Point2::new(size, (rows + 4.0) * size), size * 0.7)?;
+            self.draw_text(ctx, &format!("{}_", name), WHITE, Point2::new(size, (rows + 5.5) * size), size)?;
+        }
 
         graphics::present(ctx);
 
@@ -342,11 +591,12 @@ impl GUI {
     ///
     /// This function is used to draw the start screen.
     ///
-    pub fn draw_start_screen(&self, ctx: &mut Context, sound: bool) -> GameResult<()> {
+    /// If there is a saved game, a "Continue" button is drawn below the sound symbol.
+    ///
+    pub fn draw_start_screen(&self, ctx: &mut Context, sound: bool, can_continue: bool) -> GameResult<()> {
         graphics::clear(ctx);
 
         let size = self.block_size;
-        let columns = self.columns;
         let rows = self.rows;
         let image;
 
@@ -359,8 +609,8 @@ impl GUI {
         }
 
         graphics::set_color(ctx, WHITE)?;
-        // The Image was scaled to (width // height) = (size * (columns + 2.0) // size * (rows + 10.0))
-        let scale_x = size * (columns + 2.0) / image.width() as f32;
+        // The Image was scaled to (width // height) = (window_width // size * (rows + 10.0))
+        let scale_x = self.window_width() / image.width() as f32;
         let scale_y = size * (rows + 10.0) / image.height() as f32;
 
         let draw_param = DrawParam{
@@ -375,8 +625,157 @@ impl GUI {
 
         // Draw the image onto the screen.
         graphics::draw_ex(ctx, &image, draw_param)?;
+
+        // Draw the "Continue" button in the same orange like color as the game over standings.
+        if can_continue {
+            let button = self.continue_button();
+            self.draw_text(ctx, "Continue", Color::from_rgb(255, 153, 51), Point2::new(button.x + size, button.y), size)?;
+        }
+
+        let button = self.high_scores_button();
+        self.draw_text(ctx, "High Scores", Color::from_rgb(255, 153, 51), Point2::new(button.x, button.y), size)?;
+
+        let button = self.settings_button();
+        self.draw_text(ctx, "Settings", Color::from_rgb(255, 153, 51), Point2::new(button.x + size * 0.5, button.y), size)?;
+
+        graphics::present(ctx);
+
+        Ok(())
+    }
+
+
+    ///
+    /// This function returns the area of the "Continue" button on the start screen.
+    ///
+    pub fn continue_button(&self) -> Rect {
+        let size = self.block_size;
+        let width = self.window_width();
+
+        Rect::new(width / 4.0, self.screen_y(19.0), width * 0.5, 1.5 * size)
+    }
+
+
+    ///
+    /// This function returns the area of the "High Scores" button on the start screen, below the "Continue" button.
+    ///
+    pub fn high_scores_button(&self) -> Rect {
+        let size = self.block_size;
+        let width = self.window_width();
+
+        Rect::new(width / 4.0, self.screen_y(21.0), width * 0.5, 1.5 * size)
+    }
+
+
+    ///
+    /// This function returns the area of the "Settings" button on the start screen, below the "High Scores" button.
+    ///
+    pub fn settings_button(&self) -> Rect {
+        let size = self.block_size;
+        let width = self.window_width();
+
+        Rect::new(width / 4.0, self.screen_y(23.0), width * 0.5, 1.5 * size)
+    }
+
+
+    ///
+    /// This function draws the high score table with the given title, or a hint if there are no high scores yet.
+    ///
+    /// Every entry gets its own line with the rank, the name, the points and the completed rows.
+    ///
+    pub fn draw_high_scores(&self, ctx: &mut Context, table: Option<&HighScoreTable>) -> GameResult<()> {
+        graphics::clear(ctx);
+
+        let size = self.block_size;
+        let color = Color::from_rgb(255, 153, 51);
+
+        self.rectangle(ctx, Rect::new(0.0, 0.0, self.window_width(), (self.rows + 10.0) * size), BLACK)?;
+        self.draw_text(ctx, "High Scores", color, Point2::new(size, size), size * 1.5)?;
+
+        match table {
+            Some(table) => {
+                self.draw_text(ctx, &table.key.title(), WHITE, Point2::new(size, 3.5 * size), size * 0.6)?;
+                self.draw_text(ctx, "Name          Points    Rows", color, Point2::new(size, 5.0 * size), size * 0.7)?;
+
+                for (rank, entry) in table.entries.iter().enumerate() {
+                    let line = format!("{:>2}. {:<10} {:>8} {:>6}", rank + 1, entry.name, entry.points, entry.rows);
+                    self.draw_text(ctx, &line, WHITE, Point2::new(size, (6.5 + 1.5 * rank as f32) * size), size * 0.7)?;
+                }
+            }
+            None => self.draw_text(ctx, "No high scores yet.", WHITE, Point2::new(size, 5.0 * size), size * 0.8)?,
+        }
+
+        self.draw_text(ctx, "Left / Right: switch table", WHITE, Point2::new(size, (self.rows + 7.0) * size), size * 0.6)?;
+        self.draw_text(ctx, "Escape: back to the menu", WHITE, Point2::new(size, (self.rows + 8.0) * size), size * 0.6)?;
+
         graphics::present(ctx);
 
         Ok(())
     }
+
+
+    ///
+    /// This function draws the settings screen, every entry in its own line with its current value.
+    ///
+    /// The selected entry is drawn in orange. If "rebinding" is set, the selected action waits for a new key.
+    ///
+    pub fn draw_settings(&self, ctx: &mut Context, settings: &Settings, selected: usize, rebinding: bool) -> GameResult<()> {
+        graphics::clear(ctx);
+
+        let size = self.block_size;
+        let orange = Color::from_rgb(255, 153, 51);
+
+        self.rectangle(ctx, Rect::new(0.0, 0.0, self.window_width(), (self.rows + 10.0) * size), BLACK)?;
+        self.draw_text(ctx, "Settings", orange, Point2::new(size, size * 0.5), size * 1.5)?;
+
+        let lines = self.settings_lines(settings);
+        for (index, line) in lines.iter().enumerate() {
+            let entry = self.settings_entry(index);
+            let text = if rebinding && index == selected { format!("{}: press a key", Action::ALL[index - SETTINGS_VALUES].label()) } else { line.clone() };
+            let color = if index == selected { orange } else { WHITE };
+            self.draw_text(ctx, &text, color, Point2::new(entry.x, entry.y), size * 0.6)?;
+        }
+
+        let help_y = self.settings_entry(lines.len()).y + size * 0.5;
+        self.draw_text(ctx, "Up / Down: select, Left / Right: change", WHITE, Point2::new(size, help_y), size * 0.5)?;
+        self.draw_text(ctx, "Return: rebind, Escape: save and go back", WHITE, Point2::new(size, help_y + size * 0.8), size * 0.5)?;
+
+        graphics::present(ctx);
+
+        Ok(())
+    }
+
+
+    ///
+    /// This function returns the text of every entry of the settings screen, first the values and then the controls.
+    ///
+    fn settings_lines(&self, settings: &Settings) -> Vec<String> {
+        let mut lines = vec![
+            format!("Sound: {}", if settings.sound { "On" } else { "Off" }),
+            format!("Music volume: {}%", (settings.music_volume * 100.0).round()),
+            format!("Board rows: {}", settings.rows),
+            format!("Board columns: {}", settings.columns),
+            format!("Hidden rows: {}", settings.hidden_rows),
+            format!("Starting level: {}", settings.starting_level),
+            format!("Gravity: {:?}", settings.gravity_curve),
+            format!("Preview: {} pieces", settings.preview_size),
+            format!("DAS: {} ms", settings.handling.das),
+            format!("ARR: {} ms", settings.handling.arr),
+            format!("Soft drop factor: {}x", settings.handling.soft_drop_factor),
+        ];
+        for action in Action::ALL.iter() {
+            lines.push(format!("{}: {}", action.label(), settings.controls.keys(*action).join(", ")));
+        }
+
+        lines
+    }
+
+
+    ///
+    /// This function returns the area of the entry with the given index on the settings screen.
+    ///
+    pub fn settings_entry(&self, index: usize) -> Rect {
+        let size = self.block_size;
+
+        Rect::new(size, (2.2 + 0.75 * index as f32) * size, self.columns * size, size * 0.75)
+    }
 }
\ No newline at end of file
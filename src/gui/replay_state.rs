@@ -0,0 +1,113 @@
+// Imports.
+use ggez::{Context, GameResult};
+use ggez::event::{self, Keycode, Mod};
+use ggez::timer;
+
+use gui::GUI;
+use replay::player::ReplayPlayer;
+use settings::Settings;
+
+
+// The amount of seconds the Left and Right keys seek backwards and forwards.
+const SEEK_SECONDS: f64 = 5.0;
+
+
+///
+/// This struct represents the Replay_state, where a recorded game is played back.
+///
+/// - gui:      the information of the GUI
+/// - player:   the playback of the replay
+///
+pub struct ReplayState {
+    pub gui: GUI,
+    pub player: ReplayPlayer,
+}
+
+
+impl ReplayState {
+    pub fn new(player: ReplayPlayer) -> ReplayState {
+        // The replay is drawn in the board size and with the preview size it was recorded with.
+        let mut settings = Settings::load_or_default();
+        settings.preview_size = player.replay.rules.preview_size;
+        let gui = GUI::with_board_size(settings, player.replay.rules.rows, player.replay.rules.columns);
+        ReplayState {
+            gui,
+            player,
+        }
+    }
+
+
+    ///
+    /// This function returns the playback status, e.g. "Paused 12.3s / 60.0s x2".
+    ///
+    fn status(&self) -> String {
+        let state = if self.player.is_finished() {
+            "Finished"
+        } else if self.player.paused {
+            "Paused"
+        } else {
+            "Playing"
+        };
+        let player = if self.player.replay.autoplay { "Autoplayer" } else { "Human" };
+
+        format!("{} {:.1}s / {:.1}s x{} ({})", state, self.player.time, self.player.replay.duration(), self.player.speed, player)
+    }
+}
+
+
+///
+/// Implementing the EventHandler for the ReplayState struct.
+///
+impl event::EventHandler for ReplayState {
+    ///
+    /// The playback time moves on with the real time, times the playback speed.
+    ///
+    fn update(&mut self, ctx: &mut Context) -> GameResult<()> {
+        self.player.update(timer::duration_to_f64(timer::get_delta(ctx)));
+
+        Ok(())
+    }
+
+
+    ///
+    /// This function is used to draw the replayed game and the playback status onto the screen.
+    ///
+    fn draw(&mut self, ctx: &mut Context) -> GameResult<()> {
+        let status = self.status();
+        self.gui.draw_replay_content(&self.player.game, ctx, &status)?;
+
+        Ok(())
+    }
+
+
+    ///
+    /// This function handles key_events.
+    /// Controls are:   Space or P          - to pause and resume the playback.
+    ///                 S                   - to pause and apply exactly the next input.
+    ///                 Up and Down         - to double and halve the playback speed.
+    ///                 Left and Right      - to seek 5 seconds backwards and forwards.
+    ///                 Escape              - to quit the playback.
+    ///
+    fn key_down_event(&mut self, ctx: &mut Context, keycode: Keycode, _key_mods: Mod, _repeat: bool) {
+        match keycode {
+            Keycode::Space | Keycode::P => self.player.toggle_pause(),
+            Keycode::S => self.player.step(),
+            Keycode::Up => self.player.speed_up(),
+            Keycode::Down => self.player.slow_down(),
+            Keycode::Left => {
+                let time = self.player.time - SEEK_SECONDS;
+                self.player.seek(time);
+            }
+            Keycode::Right => {
+                let time = self.player.time + SEEK_SECONDS;
+                self.player.seek(time);
+            }
+            Keycode::Escape => {
+                if ctx.quit().is_err() {
+                    println!("Quit Error, EscapeKeyEvent in /replay_state.rs");
+                }
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,226 @@
+// Imports.
+use ggez::{Context, GameResult};
+use ggez::event::{self, Keycode, Mod, MouseButton};
+
+use gui::{GUI, SETTINGS_VALUES};
+use game::MAX_PREVIEW_SIZE;
+use settings::{Settings, MIN_ROWS, MAX_ROWS, MIN_COLUMNS, MAX_COLUMNS, MAX_HIDDEN_ROWS, MAX_STARTING_LEVEL, MAX_DAS, MAX_ARR, MAX_SOFT_DROP_FACTOR};
+use settings::controls::Action;
+use utility::gravity_curve::GravityCurve;
+
+
+// The amounts the volume, the DAS and the ARR change with every Left or Right.
+const VOLUME_STEP: f32 = 0.1;
+const DAS_STEP: f64 = 10.0;
+const ARR_STEP: f64 = 5.0;
+
+
+///
+/// This struct represents the Settings_state, it is opened from the start screen and from the pause menu.
+/// The settings are saved to the settings file when it is left.
+///
+/// - draw_once:    draw the settings just once, until something changed
+/// - settings:     the edited settings
+/// - selected:     the index of the selected entry, first the values and then the controls
+/// - rebinding:    indicates if the selected action waits for its new key
+/// - gui:          the information of the gui
+///
+pub struct SettingsState {
+    pub draw_once: bool,
+    pub settings: Settings,
+    pub selected: usize,
+    pub rebinding: bool,
+    pub gui: GUI,
+}
+
+
+impl Default for SettingsState {
+    fn default() -> SettingsState {
+        SettingsState::new()
+    }
+}
+
+
+impl SettingsState {
+    pub fn new() -> SettingsState {
+        let gui = GUI::new();
+        SettingsState {
+            draw_once: false,
+            settings: gui.settings.clone(),
+            selected: 0,
+            rebinding: false,
+            gui,
+        }
+    }
+
+
+    ///
+    /// This function returns the amount of entries, the values and one entry per action.
+    ///
+    fn entries(&self) -> usize {
+        SETTINGS_VALUES + Action::ALL.len()
+    }
+
+
+    ///
+    /// This function changes the selected value, {increase} decides the direction.
+    /// Values stay within their limits. Controls can't be changed this way, they are rebound with Return.
+    ///
+    fn change(&mut self, increase: bool) {
+        let settings = &mut self.settings;
+        match self.selected {
+            0 => settings.sound = !settings.sound,
+            1 => settings.music_volume = step_volume(settings.music_volume, increase),
+            2 => settings.rows = step(settings.rows, increase, MIN_ROWS, MAX_ROWS),
+            3 => settings.columns = step(settings.columns, increase, MIN_COLUMNS, MAX_COLUMNS),
+            4 => settings.hidden_rows = step(settings.hidden_rows, increase, 0, MAX_HIDDEN_ROWS),
+            5 => settings.starting_level = step(settings.starting_level, increase, settings.gravity_curve.first_level(), MAX_STARTING_LEVEL),
+            6 => {
+                settings.gravity_curve = step_gravity_curve(settings.gravity_curve, increase);
+                settings.starting_level = settings.starting_level.max(settings.gravity_curve.first_level());
+            }
+            7 => settings.preview_size = step(settings.preview_size, increase, 0, MAX_PREVIEW_SIZE),
+            8 => settings.handling.das = step_value(settings.handling.das, increase, DAS_STEP, 0.0, MAX_DAS),
+            9 => settings.handling.arr = step_value(settings.handling.arr, increase, ARR_STEP, 0.0, MAX_ARR),
+            10 => settings.handling.soft_drop_factor = step_value(settings.handling.soft_drop_factor, increase, 1.0, 1.0, MAX_SOFT_DROP_FACTOR),
+            _ => return,
+        }
+        self.draw_once = false;
+    }
+
+
+    ///
+    /// This function saves the settings and quits the context, so that main.rs goes on.
+    ///
+    fn back(&mut self, ctx: &mut Context) {
+        if let Err(e) = self.settings.save(&Settings::default_path()) {
+            println!("Settings Error, {} in /settings_state.rs", e);
+        }
+
+        if ctx.quit().is_err() {
+            println!("Quit Error, back() in /settings_state.rs");
+        }
+    }
+}
+
+
+///
+/// This function moves the volume one step up or down, rounded to whole percents.
+///
+fn step_volume(volume: f32, increase: bool) -> f32 {
+    let volume = if increase { volume + VOLUME_STEP } else { volume - VOLUME_STEP };
+    (volume.clamp(0.0, 1.0) * 100.0).round() / 100.0
+}
+
+
+///
+/// This function moves the value by "size" up or down, without leaving [min, max].
+///
+fn step_value(value: f64, increase: bool, size: f64, min: f64, max: f64) -> f64 {
+    let value = if increase { value + size } else { value - size };
+    value.round().clamp(min, max)
+}
+
+
+///
+/// This function moves the value one step up or down, without leaving [min, max].
+///
+fn step(value: usize, increase: bool, min: usize, max: usize) -> usize {
+    if increase { (value + 1).min(max) } else { value.saturating_sub(1).max(min) }
+}
+
+
+///
+/// This function returns the next or the previous gravity curve, after the last one the first one comes again.
+///
+fn step_gravity_curve(gravity_curve: GravityCurve, increase: bool) -> GravityCurve {
+    let curves = GravityCurve::ALL.len();
+    let index = GravityCurve::ALL.iter().position(|curve| *curve == gravity_curve).unwrap_or(0);
+    let index = if increase { index + 1 } else { index + curves - 1 };
+    GravityCurve::ALL[index % curves]
+}
+
+
+impl event::EventHandler for SettingsState {
+    fn update(&mut self, _ctx: &mut Context) -> GameResult<()> {
+        /* do nothing */
+        Ok(())
+    }
+
+
+    ///
+    /// This function draws the settings, but it draws them only once.
+    ///
+    fn draw(&mut self, ctx: &mut Context) -> GameResult<()> {
+        if !self.draw_once {
+            self.gui.draw_settings(ctx, &self.settings, self.selected, self.rebinding)?;
+            self.draw_once = true;
+        }
+
+        Ok(())
+    }
+
+
+    ///
+    /// This function handles key_events.
+    /// Controls are:   Up and Down         - to select an entry.
+    ///                 Left and Right      - to change the selected value.
+    ///                 Return              - to rebind the selected action, the next key pressed is bound to it.
+    ///                 Escape              - to save the settings and go back, or to cancel rebinding.
+    ///
+    fn key_down_event(&mut self, ctx: &mut Context, keycode: Keycode, _key_mods: Mod, _repeat: bool) {
+        self.draw_once = false;
+
+        if self.rebinding {
+            if keycode != Keycode::Escape {
+                self.settings.controls.bind(Action::ALL[self.selected - SETTINGS_VALUES], &keycode.name());
+            }
+            self.rebinding = false;
+            return;
+        }
+
+        match keycode {
+            Keycode::Up => self.selected = (self.selected + self.entries() - 1) % self.entries(),
+            Keycode::Down => self.selected = (self.selected + 1) % self.entries(),
+            Keycode::Left => self.change(false),
+            Keycode::Right => self.change(true),
+            Keycode::Return => {
+                if self.selected >= SETTINGS_VALUES {
+                    self.rebinding = true;
+                } else {
+                    self.change(true);
+                }
+            }
+            Keycode::Escape => self.back(ctx),
+            _ => {}
+        }
+    }
+
+
+    ///
+    /// This function handles mouse_events.
+    /// A click selects an entry, a click on the selected entry works like Return.
+    ///
+    fn mouse_button_down_event(&mut self, _ctx: &mut Context, button: MouseButton, x: i32, y: i32) {
+        if button != MouseButton::Left || self.rebinding {
+            return;
+        }
+
+        let x = x as f32;
+        let y = y as f32;
+        for index in 0..self.entries() {
+            let entry = self.gui.settings_entry(index);
+            if x >= entry.x && x <= entry.x + entry.w && y >= entry.y && y <= entry.y + entry.h {
+                if index != self.selected {
+                    self.selected = index;
+                } else if index >= SETTINGS_VALUES {
+                    self.rebinding = true;
+                } else {
+                    self.change(true);
+                }
+                self.draw_once = false;
+                return;
+            }
+        }
+    }
+}
@@ -1,9 +1,10 @@
 // Imports.
-use event;
-use GUI;
-use Context;
-use GameResult;
-use MouseButton;
+use ggez::{Context, GameResult};
+use ggez::event::{self, MouseButton};
+
+use gui::GUI;
+use save_game::SaveGame;
+use settings::Settings;
 
 ///
 /// Struct used to represent the Game Menu.
@@ -11,24 +12,44 @@ use MouseButton;
 /// - autoplay:     to indicate if autoplay was pressed
 /// - draw_once:    used to draw the Game Menu just once
 /// - sound:        to indicate if the sound should be played or not
-/// - was_clicked:  to differ between "autoplay" / "play" clicks and the quit_event.
+/// - was_clicked:  to differ between "autoplay" / "play" / "continue" clicks and the quit_event.
+/// - can_continue: to indicate if there is a saved game that can be continued
+/// - continue_game:to indicate if continue was pressed
+/// - show_high_scores: to indicate if high scores was pressed
+/// - show_settings: to indicate if settings was pressed
 /// - gui:          the information about the gui.
 pub struct StartState {
     pub autoplay: bool,
     pub draw_once: bool,
     pub sound: bool,
     pub was_clicked: bool,
+    pub can_continue: bool,
+    pub continue_game: bool,
+    pub show_high_scores: bool,
+    pub show_settings: bool,
     pub gui: GUI
 }
 
+impl Default for StartState {
+    fn default() -> StartState {
+        StartState::new()
+    }
+}
+
+
 impl StartState {
     pub fn new() -> StartState {
+        let gui = GUI::new();
         StartState{
             autoplay: false,
             draw_once: false,
-            sound: true,
+            sound: gui.settings.sound,
             was_clicked: false,
-            gui: GUI::new()
+            can_continue: SaveGame::default_path().exists(),
+            continue_game: false,
+            show_high_scores: false,
+            show_settings: false,
+            gui
         }
     }
 }
@@ -46,7 +67,7 @@ impl event::EventHandler for StartState {
     fn draw(&mut self, ctx: &mut Context) -> GameResult<()> {
         // Set draw_once to {true}.
         if !self.draw_once {
-            self.gui.draw_start_screen(ctx, self.sound)?;
+            self.gui.draw_start_screen(ctx, self.sound, self.can_continue)?;
             self.draw_once = true;
         }
 
@@ -56,6 +77,7 @@ impl event::EventHandler for StartState {
     ///
     /// This function handles mouse_events.
     /// Used to select "Play" for human-players and "Autoplay" for the KI run.
+    /// "Continue" is only there, if a saved game exists. "High Scores" shows the high score tables.
     ///
     fn mouse_button_down_event(&mut self, ctx: &mut Context, button: MouseButton, x: i32, y: i32) {
 
@@ -65,21 +87,24 @@ impl event::EventHandler for StartState {
         }
 
         // Button positions. ( Could be moved to the GUI )
-        let columns = self.gui.columns;
         let size = self.gui.block_size;
-        let width = (columns + 2.0) * size;
-        let height = 5.0 * size;
+        let width = self.gui.window_width();
+        let height = self.gui.screen_y(5.0);
 
         let play_x = width / 4.0;
-        let play_y = 10.0 * size;
+        let play_y = self.gui.screen_y(10.0);
 
-        let sound_x = (columns + 2.0) * size * 0.5 - 0.75 * size;
-        let sound_y = 16.0 * size;
+        let sound_x = width * 0.5 - 0.75 * size;
+        let sound_y = self.gui.screen_y(16.0);
 
         let sound_width_height = 1.5 * size;
 
         let autoplay_x = width / 4.0;
-        let autoplay_y = 13.0 * size;
+        let autoplay_y = self.gui.screen_y(13.0);
+
+        let continue_button = self.gui.continue_button();
+        let high_scores_button = self.gui.high_scores_button();
+        let settings_button = self.gui.settings_button();
 
         let x = x as f32;
         let y = y as f32;
@@ -94,14 +119,33 @@ impl event::EventHandler for StartState {
         } else if x >= autoplay_x && x <= autoplay_x + width * 0.5 && y >= autoplay_y && y <= autoplay_y + height * 0.5 {
             self.autoplay = true;
             self.was_clicked = true;
+
+            // If the continue button was clicked, continue the saved game.
+        } else if self.can_continue && x >= continue_button.x && x <= continue_button.x + continue_button.w && y >= continue_button.y && y <= continue_button.y + continue_button.h {
+            self.continue_game = true;
+            self.was_clicked = true;
+
+            // If the high scores button was clicked, show the high scores. No game is started.
+        } else if x >= high_scores_button.x && x <= high_scores_button.x + high_scores_button.w && y >= high_scores_button.y && y <= high_scores_button.y + high_scores_button.h {
+            self.show_high_scores = true;
+
+            // If the settings button was clicked, show the settings. No game is started.
+        } else if x >= settings_button.x && x <= settings_button.x + settings_button.w && y >= settings_button.y && y <= settings_button.y + settings_button.h {
+            self.show_settings = true;
             // If the sound button was clicked, set it false if it was true and vice versa.
         } else if x >= sound_x && x <= sound_x + sound_width_height && y >= sound_y && y <= sound_y + sound_width_height {
             if self.sound {
                 self.sound = false;
-                self.gui.draw_start_screen(ctx, self.sound).unwrap();
+                self.gui.draw_start_screen(ctx, self.sound, self.can_continue).unwrap();
             } else {
                 self.sound = true;
-                self.gui.draw_start_screen(ctx, self.sound).unwrap();
+                self.gui.draw_start_screen(ctx, self.sound, self.can_continue).unwrap();
+            }
+
+            // The sound symbol is the same setting as "Sound" on the settings screen, so it is saved as well.
+            self.gui.settings.sound = self.sound;
+            if let Err(e) = self.gui.settings.save(&Settings::default_path()) {
+                println!("Settings Error, {} in /gui/start_state.rs", e);
             }
             return;
         } else {
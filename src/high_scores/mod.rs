@@ -0,0 +1,293 @@
+// Imports
+use std::env;
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use dirs;
+use serde_json;
+
+use game::Game;
+use game::rules::Rules;
+
+
+// The version of the high score file format. High scores of other versions can't be loaded.
+pub const HIGH_SCORES_VERSION: u32 = 1;
+
+// The amount of entries kept in every table.
+pub const MAX_ENTRIES: usize = 10;
+
+
+// Make this struct cloneable, printable, comparable and serializable.
+// Represents what a high score table is kept for. Games only compete with games of the same key.
+//
+// - autoplay:      indicates if the table is for the autoplayer, its scores are kept apart from the human ones
+// - mode:          the piece generator and the gravity curve the games were played with
+// - rows, columns: the size of the visible board
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HighScoreKey {
+    pub autoplay: bool,
+    pub mode: String,
+    pub rows: usize,
+    pub columns: usize,
+}
+
+
+impl HighScoreKey {
+    pub fn new(game: &Game, autoplay: bool) -> HighScoreKey {
+        let rules = Rules::from_game(game);
+        HighScoreKey {
+            autoplay,
+            mode: format!("{} {:?}", rules.generator, rules.gravity_curve),
+            rows: rules.rows,
+            columns: rules.columns,
+        }
+    }
+
+
+    ///
+    /// This function returns the title of the table, e.g. "Human, seven_bag Guideline, 20x10".
+    ///
+    pub fn title(&self) -> String {
+        let player = if self.autoplay { "Autoplayer" } else { "Human" };
+        format!("{}, {}, {}x{}", player, self.mode, self.rows, self.columns)
+    }
+}
+
+
+// Make this struct cloneable, printable, comparable and serializable.
+// Represents a single high score, "time" is the unix time in seconds the game ended at.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HighScoreEntry {
+    pub name: String,
+    pub points: usize,
+    pub rows: usize,
+    pub time: u64,
+}
+
+
+impl HighScoreEntry {
+    // The entry gets the current time.
+    pub fn new(name: &str, points: usize, rows: usize) -> HighScoreEntry {
+        HighScoreEntry {
+            name: name.to_string(),
+            points,
+            rows,
+            time: SystemTime::now().duration_since(UNIX_EPOCH).map(|duration| duration.as_secs()).unwrap_or(0),
+        }
+    }
+}
+
+
+// Make this struct cloneable, printable, comparable and serializable.
+// Represents the best entries for one key, sorted by points and then by rows.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HighScoreTable {
+    pub key: HighScoreKey,
+    pub entries: Vec<HighScoreEntry>,
+}
+
+
+// Make this struct cloneable, printable, comparable and serializable.
+// Represents all high score tables, this is what is stored in the user's data directory.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HighScores {
+    pub version: u32,
+    pub tables: Vec<HighScoreTable>,
+}
+
+
+impl Default for HighScores {
+    fn default() -> HighScores {
+        HighScores::new()
+    }
+}
+
+
+impl HighScores {
+    pub fn new() -> HighScores {
+        HighScores {
+            version: HIGH_SCORES_VERSION,
+            tables: vec![],
+        }
+    }
+
+
+    ///
+    /// This function returns the table for the given key, if there is one.
+    ///
+    pub fn table(&self, key: &HighScoreKey) -> Option<&HighScoreTable> {
+        self.tables.iter().find(|table| table.key == *key)
+    }
+
+
+    ///
+    /// This function checks if a game with the given points would make it into the table of the given key.
+    ///
+    /// Returns {true} if the table isn't full yet or the points beat the last entry, {false} otherwise.
+    /// Games without any points never qualify.
+    ///
+    pub fn qualifies(&self, key: &HighScoreKey, points: usize) -> bool {
+        if points == 0 {
+            return false;
+        }
+
+        match self.table(key) {
+            Some(table) => table.entries.len() < MAX_ENTRIES || table.entries.iter().any(|entry| points > entry.points),
+            None => true,
+        }
+    }
+
+
+    ///
+    /// This function inserts the entry into the table of the given key, the table is created if needed.
+    ///
+    /// Returns the rank of the entry, starting at 0, or None if it didn't make it into the table.
+    ///
+    pub fn insert(&mut self, key: &HighScoreKey, entry: HighScoreEntry) -> Option<usize> {
+        if !self.qualifies(key, entry.points) {
+            return None;
+        }
+
+        if self.table(key).is_none() {
+            self.tables.push(HighScoreTable { key: key.clone(), entries: vec![] });
+
+            // Keep the human tables in front of the autoplayer tables.
+            self.tables.sort_by_key(|table| table.key.autoplay);
+        }
+        let table = self.tables.iter_mut().find(|table| table.key == *key).unwrap();
+
+        // Equal scores keep their order, so older entries stay in front.
+        let rank = table.entries.iter()
+            .position(|other| (entry.points, entry.rows) > (other.points, other.rows))
+            .unwrap_or(table.entries.len());
+        table.entries.insert(rank, entry);
+        table.entries.truncate(MAX_ENTRIES);
+
+        Some(rank)
+    }
+
+
+    ///
+    /// This function saves the high scores to the given path, missing directories are created.
+    ///
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(directory) = path.parent() {
+            fs::create_dir_all(directory)?;
+        }
+        let writer = BufWriter::new(File::create(path)?);
+        serde_json::to_writer_pretty(writer, self).map_err(io::Error::from)
+    }
+
+
+    ///
+    /// This function loads the high scores from the given path.
+    ///
+    /// If there is no file yet, there are no high scores yet, so empty high scores are returned.
+    /// Fails if the file is no high score file or the version doesn't match.
+    ///
+    pub fn load(path: &Path) -> io::Result<HighScores> {
+        if !path.exists() {
+            return Ok(HighScores::new());
+        }
+
+        let reader = BufReader::new(File::open(path)?);
+        let high_scores: HighScores = serde_json::from_reader(reader).map_err(io::Error::from)?;
+
+        if high_scores.version != HIGH_SCORES_VERSION {
+            let error = format!("High score version {} is not supported, expected version {}.", high_scores.version, HIGH_SCORES_VERSION);
+            return Err(io::Error::new(io::ErrorKind::InvalidData, error));
+        }
+
+        Ok(high_scores)
+    }
+
+
+    ///
+    /// This function returns the path of the high score file in the user's data directory.
+    ///
+    /// The current directory is used, if the system doesn't have a data directory.
+    ///
+    pub fn default_path() -> PathBuf {
+        let directory = dirs::data_dir().unwrap_or_else(|| env::current_dir().unwrap_or_default());
+        directory.join("tetris").join("high_scores.json")
+    }
+}
+
+
+///
+/// TESTS BEGIN HERE
+///
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::fs;
+
+    use high_scores::{HighScores, HighScoreEntry, HighScoreKey, MAX_ENTRIES, HIGH_SCORES_VERSION};
+
+    fn key(autoplay: bool) -> HighScoreKey {
+        HighScoreKey {
+            autoplay,
+            mode: "seven_bag Guideline".to_string(),
+            rows: 20,
+            columns: 10,
+        }
+    }
+
+    #[test]
+    fn entries_are_sorted_and_limited() {
+        let mut high_scores = HighScores::new();
+        for points in 1..(MAX_ENTRIES + 1) {
+            assert_eq!(high_scores.insert(&key(false), HighScoreEntry::new("A", points * 100, points)), Some(0));
+        }
+
+        // The table is full now, only better scores make it in.
+        assert!(!high_scores.qualifies(&key(false), 100));
+        assert!(high_scores.qualifies(&key(false), 150));
+        assert_eq!(high_scores.insert(&key(false), HighScoreEntry::new("B", 100, 1)), None);
+        assert_eq!(high_scores.insert(&key(false), HighScoreEntry::new("B", 550, 5)), Some(5));
+
+        let entries = &high_scores.table(&key(false)).unwrap().entries;
+        assert_eq!(entries.len(), MAX_ENTRIES);
+        assert_eq!(entries[0].points, 1000);
+        assert_eq!(entries[5].name, "B");
+        assert_eq!(entries[MAX_ENTRIES - 1].points, 200);
+    }
+
+    #[test]
+    fn autoplayer_scores_are_kept_apart() {
+        let mut high_scores = HighScores::new();
+        assert!(!high_scores.qualifies(&key(true), 0));
+
+        high_scores.insert(&key(true), HighScoreEntry::new("Autoplayer", 5000, 40));
+        high_scores.insert(&key(false), HighScoreEntry::new("A", 100, 1));
+
+        assert_eq!(high_scores.tables.len(), 2);
+        assert!(!high_scores.tables[0].key.autoplay);
+        assert_eq!(high_scores.table(&key(false)).unwrap().entries[0].points, 100);
+        assert_eq!(high_scores.table(&key(true)).unwrap().entries[0].points, 5000);
+
+        let mut other_size = key(false);
+        other_size.rows = 22;
+        assert!(high_scores.table(&other_size).is_none());
+    }
+
+    #[test]
+    fn save_and_load() {
+        let path = env::temp_dir().join("tetris_high_scores_test").join("high_scores.json");
+        let _ = fs::remove_file(&path);
+        assert_eq!(HighScores::load(&path).unwrap(), HighScores::new());
+
+        let mut high_scores = HighScores::new();
+        high_scores.insert(&key(false), HighScoreEntry::new("A", 100, 1));
+        high_scores.save(&path).unwrap();
+        assert_eq!(HighScores::load(&path).unwrap(), high_scores);
+
+        high_scores.version = HIGH_SCORES_VERSION + 1;
+        high_scores.save(&path).unwrap();
+        assert!(HighScores::load(&path).is_err());
+
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,43 @@
+// Crates included. rand => randomness for piece_spawns // rand_pcg => seedable rng for piece_spawns.
+// ggez => gui, only included with the "gui" feature, so that the engine can be used without a window or an audio device.
+// termion => terminal frontend, only included with the "tui" feature.
+#[cfg(feature = "gui")]
+extern crate ggez;
+#[cfg(feature = "tui")]
+extern crate termion;
+extern crate rand;
+extern crate rand_pcg;
+
+// serde => replay and save files.
+extern crate serde;
+#[macro_use]
+extern crate serde_derive;
+extern crate serde_json;
+
+// dirs => the user's data and config directories for the high scores and the settings.
+extern crate dirs;
+
+// toml => the settings file.
+extern crate toml;
+
+///
+/// The tetris engine: the board, the pieces, the game logic, the autoplayer, a headless simulator, a weight tuner for the autoplayer, replays, saved games, high scores, settings and the command line arguments of the binaries.
+/// The ggez GUI is only available with the "gui" feature, which is enabled by default.
+/// The terminal frontend is only available with the "tui" feature.
+///
+pub mod board;
+pub mod pieces;
+pub mod utility;
+pub mod game;
+pub mod autoplayer;
+pub mod simulator;
+pub mod tuner;
+pub mod replay;
+pub mod save_game;
+pub mod high_scores;
+pub mod settings;
+pub mod cli;
+#[cfg(feature = "gui")]
+pub mod gui;
+#[cfg(feature = "tui")]
+pub mod tui;
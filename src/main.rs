@@ -1,57 +1,144 @@
-// Crates included. ggez => gui // rand => randomness for piece_spawns.
+// Crates included. ggez => gui // tetris => the game engine and its gui.
 extern crate ggez;
-extern crate rand;
+extern crate tetris;
 
 // Imports
-use ggez::{Context, GameResult};
+use std::fs;
+use std::path::Path;
+
 use ggez::audio;
-use ggez::event::{self, Keycode, Mod, MouseButton};
-use ggez::nalgebra;
-use ggez::timer;
-
-use autoplayer::Autoplayer;
-use autoplayer::commands::Command;
-use board::Board;
-use game::Game;
-use game::move_direction::MoveDirection;
-use gui::game_over_state::GameOverState;
-use gui::GUI;
-use gui::main_state::MainState;
-use gui::start_state::StartState;
-
-mod board;
-mod pieces;
-mod utility;
-mod game;
-mod gui;
-mod autoplayer;
+use ggez::Context;
+
+use tetris::cli::Arguments;
+use tetris::gui::game_over_state::GameOverState;
+use tetris::gui::GUI;
+use tetris::gui::high_score_state::HighScoreState;
+use tetris::gui::main_state::MainState;
+use tetris::gui::replay_state::ReplayState;
+use tetris::gui::settings_state::SettingsState;
+use tetris::gui::start_state::StartState;
+use tetris::high_scores::HighScoreKey;
+use tetris::replay::Replay;
+use tetris::replay::player::ReplayPlayer;
+use tetris::save_game::SaveGame;
+use tetris::settings::Settings;
+
+
+const USAGE: &str = "Usage: tetris [OPTIONS]
+
+Options:
+    --replay <FILE>          play the recorded game back
+    --rows <ROWS>            number of visible board rows
+    --columns <COLS>         number of board columns
+    --hidden-rows <ROWS>     number of hidden rows above the visible board
+    -h, --help               print this help
+
+The board size applies to the games of this run only, the settings keep their own.";
+
+
+// Represents the board size given on the command line, it replaces the one of the settings for this run only.
+//
+// - rows:          the visible rows, if given
+// - columns:       the columns, if given
+// - hidden_rows:   the hidden rows above the visible ones, if given
+struct BoardSize {
+    rows: Option<usize>,
+    columns: Option<usize>,
+    hidden_rows: Option<usize>,
+}
+
+
+impl BoardSize {
+    ///
+    /// This function returns the settings of the settings file with the board size given on the command line.
+    /// Values are clamped to their limits.
+    ///
+    fn settings(&self) -> Settings {
+        let mut settings = Settings::load_or_default();
+        if let Some(rows) = self.rows {
+            settings.rows = rows;
+        }
+        if let Some(columns) = self.columns {
+            settings.columns = columns;
+        }
+        if let Some(hidden_rows) = self.hidden_rows {
+            settings.hidden_rows = hidden_rows;
+        }
+        settings.clamp();
+
+        settings
+    }
+}
+
 
 fn main() {
 
     // Create the gui, the game and the auto_player.
     let mut completed_rows = 0;
     let mut achieved_points = 0;
+    let mut top_out = None;
+    let mut high_score_key = None;
+
+    // "tetris --replay <file>" plays the recorded game back instead of starting a new one.
+    // "tetris --rows 20 --columns 10 --hidden-rows 2" plays on a board of the given size.
+    let mut replay = None;
+    let mut board_size = BoardSize { rows: None, columns: None, hidden_rows: None };
+    let mut arguments = Arguments::from_env(USAGE);
+    while let Some(arg) = arguments.next() {
+        match arg.as_str() {
+            "--replay" => replay = Some(arguments.value::<String>(&arg)),
+            "--rows" => board_size.rows = Some(arguments.value(&arg)),
+            "--columns" => board_size.columns = Some(arguments.value(&arg)),
+            "--hidden-rows" => board_size.hidden_rows = Some(arguments.value(&arg)),
+            "-h" | "--help" => arguments.exit_with_help(),
+            _ => arguments.exit_with_error(&format!("Unknown option \"{}\".", arg)),
+        }
+    }
 
     // Create the context with the values given in the gui.
     let ctx = &mut GUI::new().create_context();
 
+    if let Some(path) = replay {
+        play_replay(ctx, Path::new(&path));
+        return;
+    }
+
 
     // Loop every time restart was pressed in the game over menu.
     loop {
 
+        // The board size could have changed in the settings, so the window gets the size of the current settings.
+        resize_window(ctx, &GUI::new());
+
         // Create the start state and run the events_loop.
         // Its used to describe the game controls and to decide if the ki or the human wants to play the game.
         let start_state = &mut StartState::new();
         ggez::event::run(ctx, start_state).unwrap();
 
+        // Show the high scores and go back to the start screen afterwards.
+        if start_state.show_high_scores {
+            let high_score_state = &mut HighScoreState::new();
+            ggez::event::run(ctx, high_score_state).unwrap();
+            continue;
+        }
+
+        // Show the settings and go back to the start screen afterwards.
+        if start_state.show_settings {
+            let settings_state = &mut SettingsState::new();
+            ggez::event::run(ctx, settings_state).unwrap();
+            continue;
+        }
+
         // If a quit event has occurred present the game_over_screen, else keep going.
         if start_state.was_clicked {
 
             // Start playing a song as soon as the game starts.
             let mut audio = audio::Source::new(ctx, "/tetris_theme_song.ogg").unwrap();
             audio.set_repeat(true);
+            audio.set_volume(start_state.gui.settings.music_volume);
+            let mut sound = start_state.sound;
 
-            if start_state.sound {
+            if sound {
                 match audio.play() {
                     Err(_e) => {
                         println!("Audio Error, Play() in /main.rs");
@@ -61,21 +148,73 @@ fn main() {
             }
 
             // Create the state and run the events_loop.
-            let main_state = &mut MainState::new(start_state.autoplay);
-            ggez::event::run(ctx, main_state).unwrap();
+            let main_state = &mut if start_state.continue_game {
+                continue_saved_game(&board_size)
+            } else {
+                MainState::with_settings(start_state.autoplay, board_size.settings())
+            };
+            resize_window(ctx, &main_state.gui);
+
+            // Run the game again after the settings were closed and start a new one after "Restart".
+            loop {
+                ggez::event::run(ctx, main_state).unwrap();
+
+                if main_state.open_settings {
+                    main_state.open_settings = false;
+                    let settings_state = &mut SettingsState::new();
+                    ggez::event::run(ctx, settings_state).unwrap();
+
+                    // The controls apply right away, the board size, the starting level and the preview size with the next game.
+                    let settings = settings_state.settings.clone();
+                    main_state.gui.settings = settings.clone();
+                    main_state.gui.settings.preview_size = main_state.game.preview_size;
+                    main_state.auto_repeat.handling = settings.handling;
+                    audio.set_volume(settings.music_volume);
+
+                    // Start or stop the music, if the sound setting was changed.
+                    if settings.sound != sound {
+                        sound = settings.sound;
+                        if !sound {
+                            audio.stop();
+                        } else if let Err(e) = audio.play() {
+                            println!("Audio Error, {} in /main.rs", e);
+                        }
+                    }
+                } else if main_state.restart {
+                    *main_state = MainState::with_settings(main_state.autoplay, board_size.settings());
+                    resize_window(ctx, &main_state.gui);
+                } else {
+                    break;
+                }
+            }
 
             // Pause when the game is over.
-            if start_state.sound {
+            if sound {
                 audio.stop();
             }
 
+            // "Save & Quit" ends the application, the game can be continued from the start screen next time.
+            if main_state.saved {
+                break;
+            }
+
             // Update completed_rows and achieved_points.
             completed_rows = main_state.game.rows;
             achieved_points = main_state.game.points;
+            top_out = main_state.game.top_out;
+            high_score_key = Some(HighScoreKey::new(&main_state.game, main_state.autoplay));
+
+            // Save the replay of the game, so that it can be watched later.
+            let replay_path = Replay::default_path();
+            match main_state.replay.save(&replay_path) {
+                Err(e) => println!("Replay Error, {} in /main.rs", e),
+                Ok(()) => println!("Replay saved to {}", replay_path.display()),
+            }
         }
 
-        // The game is over now, so we draw the game_over_screen.
-        let game_over_state = &mut GameOverState::new(completed_rows, achieved_points);
+        // The game is over now, so we draw the game_over_screen in the size of the current settings.
+        resize_window(ctx, &GUI::new());
+        let game_over_state = &mut GameOverState::new(completed_rows, achieved_points, top_out.take(), high_score_key.take());
         ggez::event::run(ctx, game_over_state).unwrap();
 
         // Start over if the restart "button" was pressed.
@@ -84,3 +223,53 @@ fn main() {
         }
     }
 }
+
+
+///
+/// This function loads the saved game and deletes the save file, so that the game can only be continued once.
+/// If the saved game can't be loaded, a new game is started instead.
+///
+fn continue_saved_game(board_size: &BoardSize) -> MainState {
+    let path = SaveGame::default_path();
+    match SaveGame::load(&path) {
+        Ok(save_game) => {
+            if let Err(e) = fs::remove_file(&path) {
+                println!("Save Error, {} in /main.rs", e);
+            }
+            MainState::from_save(save_game)
+        }
+        Err(e) => {
+            println!("Save Error, {} in /main.rs", e);
+            MainState::with_settings(false, board_size.settings())
+        }
+    }
+}
+
+
+///
+/// This function loads the replay at the given path and plays it back.
+///
+fn play_replay(ctx: &mut Context, path: &Path) {
+    let replay = match Replay::load(path) {
+        Ok(replay) => replay,
+        Err(e) => {
+            println!("Replay Error, {} in /main.rs", e);
+            return;
+        }
+    };
+
+    // Loading already checked the piece generator, so the player can always be created.
+    let replay_state = &mut ReplayState::new(ReplayPlayer::new(replay).unwrap());
+    resize_window(ctx, &replay_state.gui);
+    ggez::event::run(ctx, replay_state).unwrap();
+}
+
+
+///
+/// This function resizes the window to the board size of the given gui.
+///
+fn resize_window(ctx: &mut Context, gui: &GUI) {
+    if let Err(e) = gui.resize_window(ctx) {
+        println!("Window Error, {} in /main.rs", e);
+    }
+}
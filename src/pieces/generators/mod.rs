@@ -0,0 +1,251 @@
+//!
+//! This module contains the different randomizers that decide which piece comes next.
+//!
+//! random_generator:       every piece_type with the same probability, independent of the previous pieces
+//! seven_bag_generator:    the guideline randomizer, every piece_type once in a shuffled bag of seven
+//! nes_generator:          the randomizer of the original NES Tetris, rerolls once on a repeat
+//! tgm_generator:          the randomizer of Tetris The Grand Master, rerolls pieces in a history of four
+//!
+
+pub mod random_generator;
+pub mod seven_bag_generator;
+pub mod nes_generator;
+pub mod tgm_generator;
+
+// Imports
+use std::fmt::Debug;
+
+use rand::RngCore;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use pieces::piece::Piece;
+use self::random_generator::RandomGenerator;
+use self::seven_bag_generator::SevenBagGenerator;
+use self::nes_generator::NesGenerator;
+use self::tgm_generator::TgmGenerator;
+
+
+///
+/// Every piece generator has to implement this trait.
+///
+/// The generators have to be printable and cloneable, because the game is.
+///
+pub trait PieceGenerator: Debug {
+    ///
+    /// This function returns the next piece of the sequence.
+    ///
+    /// All the randomness has to come from the given rng, so that seeded games can be reproduced.
+    ///
+    fn next_piece(&mut self, rng: &mut dyn RngCore) -> Piece;
+
+
+    ///
+    /// This function returns a boxed clone of the generator.
+    ///
+    fn box_clone(&self) -> Box<dyn PieceGenerator>;
+
+
+    ///
+    /// This function returns the name of the generator, which is used to store it in files.
+    ///
+    fn name(&self) -> &'static str;
+
+
+    ///
+    /// This function returns a copy of the generator and its state, which is used to store it in files.
+    ///
+    fn state(&self) -> GeneratorState;
+}
+
+
+// Make this enum cloneable, printable and serializable.
+// Represents one of the generators together with its state, e.g. the pieces left in the bag.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum GeneratorState {
+    Random(RandomGenerator),
+    SevenBag(SevenBagGenerator),
+    Nes(NesGenerator),
+    Tgm(TgmGenerator),
+}
+
+
+impl GeneratorState {
+    ///
+    /// This function turns the state back into a generator, which continues exactly where the stored one stopped.
+    ///
+    pub fn into_generator(self) -> Box<dyn PieceGenerator> {
+        match self {
+            GeneratorState::Random(generator) => Box::new(generator),
+            GeneratorState::SevenBag(generator) => Box::new(generator),
+            GeneratorState::Nes(generator) => Box::new(generator),
+            GeneratorState::Tgm(generator) => Box::new(generator),
+        }
+    }
+}
+
+
+impl Clone for Box<dyn PieceGenerator> {
+    fn clone(&self) -> Box<dyn PieceGenerator> {
+        self.box_clone()
+    }
+}
+
+
+///
+/// This function returns a new generator for the given name or None if there is no generator with that name.
+///
+pub fn get_generator(name: &str) -> Option<Box<dyn PieceGenerator>> {
+    match name {
+        "random" => Some(Box::new(RandomGenerator::new())),
+        "seven_bag" => Some(Box::new(SevenBagGenerator::new())),
+        "nes" => Some(Box::new(NesGenerator::new())),
+        "tgm" => Some(Box::new(TgmGenerator::new())),
+        _ => None,
+    }
+}
+
+
+///
+/// This function is used by serde to store a boxed generator with its state, see #[serde(with)] in Game.
+///
+#[allow(clippy::borrowed_box)]
+pub fn serialize<S: Serializer>(generator: &Box<dyn PieceGenerator>, serializer: S) -> Result<S::Ok, S::Error> {
+    generator.state().serialize(serializer)
+}
+
+
+///
+/// This function is used by serde to load a boxed generator with its state, see #[serde(with)] in Game.
+///
+pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Box<dyn PieceGenerator>, D::Error> {
+    GeneratorState::deserialize(deserializer).map(GeneratorState::into_generator)
+}
+
+
+
+///
+/// TESTS BEGIN HERE
+///
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+    use rand_pcg::Pcg32;
+
+    use pieces::generators::{get_generator, PieceGenerator};
+    use pieces::generators::random_generator::RandomGenerator;
+    use pieces::generators::seven_bag_generator::SevenBagGenerator;
+    use pieces::generators::nes_generator::NesGenerator;
+    use pieces::generators::tgm_generator::TgmGenerator;
+    use pieces::piece_type::PieceType;
+
+    const SAMPLES: usize = 7000;
+
+    fn index(piece_type: &PieceType) -> usize {
+        match piece_type {
+            PieceType::I => 0,
+            PieceType::J => 1,
+            PieceType::L => 2,
+            PieceType::O => 3,
+            PieceType::S => 4,
+            PieceType::T => 5,
+            PieceType::Z => 6,
+            PieceType::Garbage | PieceType::None => panic!("A generator returned {:?}.", piece_type),
+        }
+    }
+
+    fn sequence(generator: &mut dyn PieceGenerator, length: usize) -> Vec<PieceType> {
+        let mut rng = Pcg32::seed_from_u64(42);
+        (0..length).map(|_| generator.next_piece(&mut rng).piece_type).collect()
+    }
+
+    fn counts(sequence: &[PieceType]) -> Vec<usize> {
+        let mut counts = vec![0; 7];
+        for piece_type in sequence {
+            counts[index(piece_type)] += 1;
+        }
+        counts
+    }
+
+    fn repeat_rate(sequence: &[PieceType]) -> f64 {
+        let repeats = sequence.windows(2).filter(|pair| pair[0] == pair[1]).count();
+        repeats as f64 / (sequence.len() - 1) as f64
+    }
+
+    #[test]
+    fn random_is_uniform() {
+        let sequence = sequence(&mut RandomGenerator::new(), SAMPLES);
+        for count in counts(&sequence) {
+            assert!(count > 850 && count < 1150, "count {} is not uniform", count);
+        }
+        let rate = repeat_rate(&sequence);
+        assert!(rate > 0.11 && rate < 0.18, "repeat rate {}", rate);
+    }
+
+    #[test]
+    fn seven_bag_contains_every_piece_once() {
+        let sequence = sequence(&mut SevenBagGenerator::new(), SAMPLES);
+        for bag in sequence.chunks(7) {
+            assert_eq!(counts(bag), vec![1; 7]);
+        }
+
+        // The longest possible gap between two equal pieces is 12 pieces.
+        let mut last_seen = [0; 7];
+        for (position, piece_type) in sequence.iter().enumerate() {
+            assert!(position - last_seen[index(piece_type)] <= 13);
+            last_seen[index(piece_type)] = position;
+        }
+    }
+
+    #[test]
+    fn nes_reduces_repeats() {
+        let sequence = sequence(&mut NesGenerator::new(), SAMPLES);
+        for count in counts(&sequence) {
+            assert!(count > 750 && count < 1250, "count {} is too far off", count);
+        }
+
+        // A repeat needs two matching rolls, the expected rate is 1/28.
+        let rate = repeat_rate(&sequence);
+        assert!(rate > 0.015 && rate < 0.06, "repeat rate {}", rate);
+    }
+
+    #[test]
+    fn same_seed_same_sequence() {
+        let generators: Vec<Box<dyn PieceGenerator>> = vec![
+            Box::new(RandomGenerator::new()),
+            Box::new(SevenBagGenerator::new()),
+            Box::new(NesGenerator::new()),
+            Box::new(TgmGenerator::new()),
+        ];
+        for generator in generators {
+            let first = sequence(&mut *generator.clone(), 100);
+            let second = sequence(&mut *generator.clone(), 100);
+            assert_eq!(first, second);
+        }
+    }
+
+    #[test]
+    fn tgm_avoids_recent_pieces() {
+        for seed in 0..20 {
+            let first = TgmGenerator::new().next_piece(&mut Pcg32::seed_from_u64(seed)).piece_type;
+            assert!(first != PieceType::S && first != PieceType::Z && first != PieceType::O);
+        }
+
+        let sequence = sequence(&mut TgmGenerator::new(), SAMPLES);
+        for count in counts(&sequence) {
+            assert!(count > 750 && count < 1250, "count {} is too far off", count);
+        }
+
+        // Count the pieces that appeared in the previous four pieces, which is about 48% for a uniform randomizer.
+        let recent = sequence.windows(5).filter(|window| window[..4].contains(&window[4])).count();
+        let rate = recent as f64 / (sequence.len() - 4) as f64;
+        assert!(rate < 0.15, "history rate {}", rate);
+    }
+
+    #[test]
+    fn generators_are_found_by_name() {
+        for name in ["random", "seven_bag", "nes", "tgm"].iter() {
+            assert_eq!(get_generator(name).unwrap().name(), *name);
+        }
+        assert!(get_generator("unknown").is_none());
+    }
+}
@@ -0,0 +1,67 @@
+// Imports
+use rand::{Rng, RngCore};
+
+use pieces;
+use pieces::piece::Piece;
+use pieces::piece_type::PieceType;
+use pieces::generators::{GeneratorState, PieceGenerator};
+
+
+// Make this struct cloneable, printable and serializable.
+// Represents the generator of the original NES Tetris, which remembers the last piece_type.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NesGenerator {
+    pub last_piece_type: PieceType,
+}
+
+
+impl Default for NesGenerator {
+    fn default() -> NesGenerator {
+        NesGenerator::new()
+    }
+}
+
+
+impl NesGenerator {
+    pub fn new() -> NesGenerator {
+        NesGenerator {
+            last_piece_type: PieceType::None,
+        }
+    }
+}
+
+
+impl PieceGenerator for NesGenerator {
+    ///
+    /// The NES rolls a number out of eight. If it rolls the eighth "dummy" number or the last piece_type,
+    /// it rolls once more out of the seven piece_types and takes whatever comes.
+    ///
+    fn next_piece(&mut self, rng: &mut dyn RngCore) -> Piece {
+        let mut piece = match rng.gen_range(0, 8) {
+            7 => Piece::empty_piece(),
+            number => pieces::get_piece_by_number(number),
+        };
+
+        if piece.piece_type == PieceType::None || piece.piece_type == self.last_piece_type {
+            piece = pieces::get_piece_by_number(rng.gen_range(0, 7));
+        }
+
+        self.last_piece_type = piece.piece_type.clone();
+        piece
+    }
+
+
+    fn box_clone(&self) -> Box<dyn PieceGenerator> {
+        Box::new(self.clone())
+    }
+
+
+    fn name(&self) -> &'static str {
+        "nes"
+    }
+
+
+    fn state(&self) -> GeneratorState {
+        GeneratorState::Nes(self.clone())
+    }
+}
@@ -0,0 +1,48 @@
+// Imports
+use rand::RngCore;
+
+use pieces;
+use pieces::piece::Piece;
+use pieces::generators::{GeneratorState, PieceGenerator};
+
+
+// Make this struct cloneable, printable and serializable.
+// Represents the pure random generator, where every piece_type has the same probability.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RandomGenerator;
+
+
+impl Default for RandomGenerator {
+    fn default() -> RandomGenerator {
+        RandomGenerator::new()
+    }
+}
+
+
+impl RandomGenerator {
+    pub fn new() -> RandomGenerator {
+        RandomGenerator
+    }
+}
+
+
+impl PieceGenerator for RandomGenerator {
+    fn next_piece(&mut self, rng: &mut dyn RngCore) -> Piece {
+        pieces::get_next_random_piece(rng)
+    }
+
+
+    fn box_clone(&self) -> Box<dyn PieceGenerator> {
+        Box::new(self.clone())
+    }
+
+
+    fn name(&self) -> &'static str {
+        "random"
+    }
+
+
+    fn state(&self) -> GeneratorState {
+        GeneratorState::Random(self.clone())
+    }
+}
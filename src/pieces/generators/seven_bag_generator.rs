@@ -0,0 +1,68 @@
+// Imports
+use rand::RngCore;
+use rand::seq::SliceRandom;
+
+use pieces;
+use pieces::piece::Piece;
+use pieces::piece_type::PieceType;
+use pieces::generators::{GeneratorState, PieceGenerator};
+
+
+// Make this struct cloneable, printable and serializable.
+// Represents the guideline 7-bag generator.
+// All seven piece_types are put into a bag, which is shuffled and emptied before a new bag is filled.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SevenBagGenerator {
+    pub bag: Vec<PieceType>,
+}
+
+
+impl Default for SevenBagGenerator {
+    fn default() -> SevenBagGenerator {
+        SevenBagGenerator::new()
+    }
+}
+
+
+impl SevenBagGenerator {
+    pub fn new() -> SevenBagGenerator {
+        SevenBagGenerator {
+            bag: vec![],
+        }
+    }
+
+
+    ///
+    /// This function fills the bag with every piece_type once and shuffles it.
+    ///
+    fn fill_bag(&mut self, rng: &mut dyn RngCore) {
+        self.bag = vec![PieceType::I, PieceType::J, PieceType::L, PieceType::O, PieceType::S, PieceType::T, PieceType::Z];
+        self.bag.shuffle(rng);
+    }
+}
+
+
+impl PieceGenerator for SevenBagGenerator {
+    fn next_piece(&mut self, rng: &mut dyn RngCore) -> Piece {
+        if self.bag.is_empty() {
+            self.fill_bag(rng);
+        }
+
+        pieces::get_piece(&self.bag.pop().unwrap())
+    }
+
+
+    fn box_clone(&self) -> Box<dyn PieceGenerator> {
+        Box::new(self.clone())
+    }
+
+
+    fn name(&self) -> &'static str {
+        "seven_bag"
+    }
+
+
+    fn state(&self) -> GeneratorState {
+        GeneratorState::SevenBag(self.clone())
+    }
+}
@@ -0,0 +1,82 @@
+// Imports
+use rand::{Rng, RngCore};
+
+use pieces;
+use pieces::piece::Piece;
+use pieces::piece_type::PieceType;
+use pieces::generators::{GeneratorState, PieceGenerator};
+
+
+// The number of rolls before the generator gives up and takes the last roll.
+const ROLLS: usize = 4;
+
+
+// Make this struct cloneable, printable and serializable.
+// Represents the generator of Tetris The Grand Master, which remembers the last four piece_types.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TgmGenerator {
+    pub history: Vec<PieceType>,
+    pub first_piece: bool,
+}
+
+
+impl Default for TgmGenerator {
+    fn default() -> TgmGenerator {
+        TgmGenerator::new()
+    }
+}
+
+
+impl TgmGenerator {
+    // The history starts filled with Z pieces.
+    pub fn new() -> TgmGenerator {
+        TgmGenerator {
+            history: vec![PieceType::Z; 4],
+            first_piece: true,
+        }
+    }
+}
+
+
+impl PieceGenerator for TgmGenerator {
+    ///
+    /// The generator rolls up to four times until it gets a piece_type that is not in the history.
+    /// The very first piece is never an S, Z or O piece.
+    ///
+    fn next_piece(&mut self, rng: &mut dyn RngCore) -> Piece {
+        let mut piece;
+
+        if self.first_piece {
+            self.first_piece = false;
+            piece = pieces::get_piece(&[PieceType::I, PieceType::J, PieceType::L, PieceType::T][rng.gen_range(0, 4)]);
+        } else {
+            piece = pieces::get_piece_by_number(rng.gen_range(0, 7));
+            for _ in 1..ROLLS {
+                if !self.history.contains(&piece.piece_type) {
+                    break;
+                }
+                piece = pieces::get_piece_by_number(rng.gen_range(0, 7));
+            }
+        }
+
+        // Forget the oldest piece_type and remember the new one.
+        self.history.remove(0);
+        self.history.push(piece.piece_type.clone());
+        piece
+    }
+
+
+    fn box_clone(&self) -> Box<dyn PieceGenerator> {
+        Box::new(self.clone())
+    }
+
+
+    fn name(&self) -> &'static str {
+        "tgm"
+    }
+
+
+    fn state(&self) -> GeneratorState {
+        GeneratorState::Tgm(self.clone())
+    }
+}
@@ -1,5 +1,11 @@
+// The module banners below aren't the doc comments of the items after them.
+#![allow(clippy::empty_line_after_doc_comments)]
+
 pub mod piece;
 pub mod piece_type;
+pub mod generators;
+pub mod rotation_state;
+pub mod wall_kicks;
 
 // Imports
 use rand::Rng;
@@ -12,6 +18,7 @@ use utility::matrix::Matrix;
 
 ///
 /// This module should represent a piece_factory.
+/// The default pieces have the guideline shapes of the SRS state 0, every piece spawns in it.
 ///
 
 
@@ -19,12 +26,12 @@ use utility::matrix::Matrix;
 /// This function creates the default IPiece.
 ///
 pub fn get_i_piece() -> Piece {
-    let point = Point::new(1, 0);
-    let mut matrix = Matrix::new(4, 1);
+    let point = Point::new(0, 1);
+    let mut matrix = Matrix::new(1, 4);
     matrix.data[0][0] = true;
-    matrix.data[1][0] = true;
-    matrix.data[2][0] = true;
-    matrix.data[3][0] = true;
+    matrix.data[0][1] = true;
+    matrix.data[0][2] = true;
+    matrix.data[0][3] = true;
 
     Piece::new(PieceType::I,matrix, point)
 }
@@ -35,13 +42,13 @@ pub fn get_i_piece() -> Piece {
 ///
 pub fn get_j_piece() -> Piece {
     let point = Point::new(1, 1);
-    let mut matrix = Matrix::new(3, 2);
-    matrix.data[0][0] = false;
-    matrix.data[0][1] = true;
-    matrix.data[1][0] = false;
+    let mut matrix = Matrix::new(2, 3);
+    matrix.data[0][0] = true;
+    matrix.data[0][1] = false;
+    matrix.data[0][2] = false;
+    matrix.data[1][0] = true;
     matrix.data[1][1] = true;
-    matrix.data[2][0] = true;
-    matrix.data[2][1] = true;
+    matrix.data[1][2] = true;
 
     Piece::new(PieceType::J,matrix, point)
 }
@@ -51,14 +58,14 @@ pub fn get_j_piece() -> Piece {
 /// This function creates the default LPiece.
 ///
 pub fn get_l_piece() -> Piece {
-    let point = Point::new(1, 0);
-    let mut matrix = Matrix::new(3, 2);
-    matrix.data[0][0] = true;
+    let point = Point::new(1, 1);
+    let mut matrix = Matrix::new(2, 3);
+    matrix.data[0][0] = false;
     matrix.data[0][1] = false;
+    matrix.data[0][2] = true;
     matrix.data[1][0] = true;
-    matrix.data[1][1] = false;
-    matrix.data[2][0] = true;
-    matrix.data[2][1] = true;
+    matrix.data[1][1] = true;
+    matrix.data[1][2] = true;
 
     Piece::new(PieceType::L,matrix, point)
 }
@@ -100,14 +107,14 @@ pub fn get_s_piece() -> Piece {
 /// This function creates the default TPiece.
 ///
 pub fn get_t_piece() -> Piece {
-    let point = Point::new(0, 1);
+    let point = Point::new(1, 1);
     let mut matrix = Matrix::new(2, 3);
-    matrix.data[0][0] = true;
+    matrix.data[0][0] = false;
     matrix.data[0][1] = true;
-    matrix.data[0][2] = true;
-    matrix.data[1][0] = false;
+    matrix.data[0][2] = false;
+    matrix.data[1][0] = true;
     matrix.data[1][1] = true;
-    matrix.data[1][2] = false;
+    matrix.data[1][2] = true;
 
     Piece::new(PieceType::T,matrix, point)
 }
@@ -131,11 +138,27 @@ pub fn get_z_piece() -> Piece {
 
 
 ///
-/// This function creates a new random_piece.
+/// This function creates the default piece of the given piece_type.
 ///
-pub fn get_next_random_piece() -> Piece {
-    let random_number = rand::thread_rng().gen_range(0, 7);
-    match random_number {
+pub fn get_piece(piece_type: &PieceType) -> Piece {
+    match piece_type {
+        PieceType::I => get_i_piece(),
+        PieceType::J => get_j_piece(),
+        PieceType::L => get_l_piece(),
+        PieceType::O => get_o_piece(),
+        PieceType::S => get_s_piece(),
+        PieceType::T => get_t_piece(),
+        PieceType::Z => get_z_piece(),
+        PieceType::Garbage | PieceType::None => Piece::empty_piece(),
+    }
+}
+
+
+///
+/// This function creates the default piece for the given number between 0 and 6.
+///
+pub fn get_piece_by_number(number: usize) -> Piece {
+    match number {
         0 => get_i_piece(),
         1 => get_j_piece(),
         2 => get_l_piece(),
@@ -148,6 +171,14 @@ pub fn get_next_random_piece() -> Piece {
 }
 
 
+///
+/// This function creates a new random_piece with the given rng.
+///
+pub fn get_next_random_piece<R: Rng + ?Sized>(rng: &mut R) -> Piece {
+    get_piece_by_number(rng.gen_range(0, 7))
+}
+
+
 
 ///
 /// TESTS BEGIN HERE
@@ -164,12 +195,9 @@ mod tests {
 
     #[test]
     fn piece_i() {
-        let point = Point::new(1, 0);
-        let mut m = Matrix::new(4, 1);
-        m.data[0][0] = true;
-        m.data[1][0] = true;
-        m.data[2][0] = true;
-        m.data[3][0] = true;
+        let point = Point::new(0, 1);
+        let mut m = Matrix::new(1, 4);
+        m.data[0][0] = true; m.data[0][1] = true; m.data[0][2] = true; m.data[0][3] = true;
 
         assert_eq!(Piece::new(PieceType::I,m, point), get_i_piece());
     }
@@ -177,21 +205,19 @@ mod tests {
     #[test]
     fn piece_j() {
         let point = Point::new(1, 1);
-        let mut m = Matrix::new(3, 2);
-        m.data[0][0] = false; m.data[0][1] = true;
-        m.data[1][0] = false; m.data[1][1] = true;
-        m.data[2][0] = true;  m.data[2][1] = true;
+        let mut m = Matrix::new(2, 3);
+        m.data[0][0] = true; m.data[0][1] = false; m.data[0][2] = false;
+        m.data[1][0] = true; m.data[1][1] = true; m.data[1][2] = true;
 
         assert_eq!(Piece::new(PieceType::J,m, point), get_j_piece());
     }
 
     #[test]
     fn piece_l() {
-        let point = Point::new(1, 0);
-        let mut m = Matrix::new(3, 2);
-        m.data[0][0] = true; m.data[0][1] = false;
-        m.data[1][0] = true; m.data[1][1] = false;
-        m.data[2][0] = true;  m.data[2][1] = true;
+        let point = Point::new(1, 1);
+        let mut m = Matrix::new(2, 3);
+        m.data[0][0] = false; m.data[0][1] = false; m.data[0][2] = true;
+        m.data[1][0] = true; m.data[1][1] = true; m.data[1][2] = true;
 
         assert_eq!(Piece::new(PieceType::L,m, point), get_l_piece());
     }
@@ -218,10 +244,10 @@ mod tests {
 
     #[test]
     fn piece_t() {
-        let point = Point::new(0, 1);
+        let point = Point::new(1, 1);
         let mut m = Matrix::new(2, 3);
-        m.data[0][0] = true; m.data[0][1] = true; m.data[0][2] = true;
-        m.data[1][0] = false; m.data[1][1] = true; m.data[1][2] = false;
+        m.data[0][0] = false; m.data[0][1] = true; m.data[0][2] = false;
+        m.data[1][0] = true; m.data[1][1] = true; m.data[1][2] = true;
 
         assert_eq!(Piece::new(PieceType::T,m, point), get_t_piece());
     }
@@ -243,13 +269,26 @@ mod tests {
 
         //first rotation
         piece.perform_clockwise_rotation();
+
+        let point = Point::new(1, 0);
+        let mut m = Matrix::new(4, 1);
+        m.data[0][0] = true;
+        m.data[1][0] = true;
+        m.data[2][0] = true;
+        m.data[3][0] = true;
+
+        assert_eq!(Piece::new(PieceType::I,m, point),piece);
+
+        //second rotation
+        piece.perform_clockwise_rotation();
+
         let point = Point::new(0, 2);
         let mut m = Matrix::new(1, 4);
         m.data[0][0] = true; m.data[0][1]=true; m.data[0][2]=true; m.data[0][3]=true;
 
         assert_eq!(Piece::new(PieceType::I,m, point),piece);
 
-        //second rotation
+        //third rotation
         piece.perform_clockwise_rotation();
 
         let point = Point::new(2, 0);
@@ -261,16 +300,6 @@ mod tests {
 
         assert_eq!(Piece::new(PieceType::I,m, point),piece);
 
-
-        //third rotation
-        piece.perform_clockwise_rotation();
-
-        let point = Point::new(0, 1);
-        let mut m = Matrix::new(1, 4);
-        m.data[0][0] = true; m.data[0][1]=true; m.data[0][2]=true; m.data[0][3]=true;
-
-        assert_eq!(Piece::new(PieceType::I,m, point),piece);
-
         //back to start rotation
         piece.perform_clockwise_rotation();
         assert_eq!(get_i_piece(),piece);
@@ -284,25 +313,15 @@ mod tests {
         //first rotation
         piece.perform_clockwise_rotation();
 
-        let point = Point::new(1, 1);
-        let mut m = Matrix::new(2, 3);
-        m.data[0][0] = true; m.data[0][1] = false; m.data[0][2]=false;
-        m.data[1][0] = true; m.data[1][1] = true; m.data[1][2]=true;
-
-        assert_eq!(Piece::new(PieceType::J,m,point),piece);
-
-        //second rotation
-        piece.perform_clockwise_rotation();
-
         let point = Point::new(1, 0);
         let mut m = Matrix::new(3, 2);
         m.data[0][0] = true; m.data[0][1] = true;
         m.data[1][0] = true; m.data[1][1] = false;
         m.data[2][0] = true;  m.data[2][1] = false;
 
-        assert_eq!(Piece::new(PieceType::J,m,point),piece);
+        assert_eq!(Piece::new(PieceType::J,m, point),piece);
 
-        //third rotation
+        //second rotation
         piece.perform_clockwise_rotation();
 
         let point = Point::new(0, 1);
@@ -310,7 +329,18 @@ mod tests {
         m.data[0][0] = true; m.data[0][1] = true; m.data[0][2]=true;
         m.data[1][0] = false; m.data[1][1] = false; m.data[1][2]=true;
 
-        assert_eq!(Piece::new(PieceType::J,m,point),piece);
+        assert_eq!(Piece::new(PieceType::J,m, point),piece);
+
+        //third rotation
+        piece.perform_clockwise_rotation();
+
+        let point = Point::new(1, 1);
+        let mut m = Matrix::new(3, 2);
+        m.data[0][0] = false; m.data[0][1] = true;
+        m.data[1][0] = false; m.data[1][1] = true;
+        m.data[2][0] = true;  m.data[2][1] = true;
+
+        assert_eq!(Piece::new(PieceType::J,m, point),piece);
 
         //back to start rotation
         piece.perform_clockwise_rotation();
@@ -325,14 +355,25 @@ mod tests {
         //first rotation
         piece.perform_clockwise_rotation();
 
+        let point = Point::new(1, 0);
+        let mut m = Matrix::new(3, 2);
+        m.data[0][0] = true; m.data[0][1] = false;
+        m.data[1][0] = true; m.data[1][1] = false;
+        m.data[2][0] = true;  m.data[2][1] = true;
+
+        assert_eq!(Piece::new(PieceType::L,m, point),piece);
+
+        //second rotation
+        piece.perform_clockwise_rotation();
+
         let point = Point::new(0, 1);
         let mut m = Matrix::new(2, 3);
         m.data[0][0] = true; m.data[0][1] = true; m.data[0][2]=true;
         m.data[1][0] = true; m.data[1][1] = false; m.data[1][2]=false;
 
-        assert_eq!(Piece::new(PieceType::L,m,point),piece);
+        assert_eq!(Piece::new(PieceType::L,m, point),piece);
 
-        //second rotation
+        //third rotation
         piece.perform_clockwise_rotation();
 
         let point = Point::new(1, 1);
@@ -341,22 +382,10 @@ mod tests {
         m.data[1][0] = false; m.data[1][1] = true;
         m.data[2][0] = false;  m.data[2][1] = true;
 
-        assert_eq!(Piece::new(PieceType::L,m,point),piece);
-
-        //third rotation
-        piece.perform_clockwise_rotation();
-
-        let point = Point::new(1, 1);
-        let mut m = Matrix::new(2, 3);
-        m.data[0][0] = false; m.data[0][1] = false; m.data[0][2]=true;
-        m.data[1][0] = true; m.data[1][1] = true; m.data[1][2]=true;
-
-        assert_eq!(Piece::new(PieceType::L,m,point),piece);
+        assert_eq!(Piece::new(PieceType::L,m, point),piece);
 
         //back to start rotation
-
         piece.perform_clockwise_rotation();
-
         assert_eq!(get_l_piece(),piece);
     }
 
@@ -448,37 +477,37 @@ mod tests {
         //first rotation
         piece.perform_clockwise_rotation();
 
-        let point = Point::new(1, 1);
+        let point = Point::new(1, 0);
         let mut m = Matrix::new(3, 2);
-        m.data[0][0] = false; m.data[0][1] = true;
+        m.data[0][0] = true; m.data[0][1] = false;
         m.data[1][0] = true; m.data[1][1] = true;
-        m.data[2][0] = false; m.data[2][1] = true;
+        m.data[2][0] = true; m.data[2][1] = false;
+
+        assert_eq!(Piece::new(PieceType::T,m, point),piece);
 
-        assert_eq!(Piece::new(PieceType::T,m,point),piece);
         //second rotation
         piece.perform_clockwise_rotation();
 
-        let point = Point::new(1, 1);
+        let point = Point::new(0, 1);
         let mut m = Matrix::new(2, 3);
-        m.data[0][0] = false; m.data[0][1] = true; m.data[0][2] = false;
-        m.data[1][0] = true; m.data[1][1] = true; m.data[1][2] = true;
+        m.data[0][0] = true; m.data[0][1] = true; m.data[0][2] = true;
+        m.data[1][0] = false; m.data[1][1] = true; m.data[1][2] = false;
 
-        assert_eq!(Piece::new(PieceType::T,m,point),piece);
+        assert_eq!(Piece::new(PieceType::T,m, point),piece);
 
         //third rotation
         piece.perform_clockwise_rotation();
 
-        let point = Point::new(1, 0);
+        let point = Point::new(1, 1);
         let mut m = Matrix::new(3, 2);
-        m.data[0][0] = true; m.data[0][1] = false;
+        m.data[0][0] = false; m.data[0][1] = true;
         m.data[1][0] = true; m.data[1][1] = true;
-        m.data[2][0] = true; m.data[2][1] = false;
+        m.data[2][0] = false; m.data[2][1] = true;
 
-        assert_eq!(Piece::new(PieceType::T,m,point),piece);
+        assert_eq!(Piece::new(PieceType::T,m, point),piece);
 
         //back to start rotation
         piece.perform_clockwise_rotation();
-
         assert_eq!(get_t_piece(),piece);
     }
 
@@ -533,15 +562,6 @@ mod tests {
         //first rotation
         piece.perform_counter_clockwise_rotation();
 
-        let point = Point::new(0, 1);
-        let mut m = Matrix::new(1, 4);
-        m.data[0][0] = true; m.data[0][1]=true; m.data[0][2]=true; m.data[0][3]=true;
-
-        assert_eq!(Piece::new(PieceType::I,m, point),piece);
-
-        //second rotation
-        piece.perform_counter_clockwise_rotation();
-
         let point = Point::new(2, 0);
         let mut m = Matrix::new(4, 1);
         m.data[0][0] = true;
@@ -551,14 +571,24 @@ mod tests {
 
         assert_eq!(Piece::new(PieceType::I,m, point),piece);
 
-
-        //third rotation
+        //second rotation
         piece.perform_counter_clockwise_rotation();
 
         let point = Point::new(0, 2);
         let mut m = Matrix::new(1, 4);
         m.data[0][0] = true; m.data[0][1]=true; m.data[0][2]=true; m.data[0][3]=true;
 
+        assert_eq!(Piece::new(PieceType::I,m, point),piece);
+
+        //third rotation
+        piece.perform_counter_clockwise_rotation();
+
+        let point = Point::new(1, 0);
+        let mut m = Matrix::new(4, 1);
+        m.data[0][0] = true;
+        m.data[1][0] = true;
+        m.data[2][0] = true;
+        m.data[3][0] = true;
 
         assert_eq!(Piece::new(PieceType::I,m, point),piece);
 
@@ -574,14 +604,25 @@ mod tests {
         //first rotation
         piece.perform_counter_clockwise_rotation();
 
+        let point = Point::new(1, 1);
+        let mut m = Matrix::new(3, 2);
+        m.data[0][0] = false; m.data[0][1] = true;
+        m.data[1][0] = false; m.data[1][1] = true;
+        m.data[2][0] = true;  m.data[2][1] = true;
+
+        assert_eq!(Piece::new(PieceType::J,m, point),piece);
+
+        //second rotation
+        piece.perform_counter_clockwise_rotation();
+
         let point = Point::new(0, 1);
         let mut m = Matrix::new(2, 3);
         m.data[0][0] = true; m.data[0][1] = true; m.data[0][2]=true;
         m.data[1][0] = false; m.data[1][1] = false; m.data[1][2]=true;
 
-        assert_eq!(Piece::new(PieceType::J,m,point),piece);
+        assert_eq!(Piece::new(PieceType::J,m, point),piece);
 
-        //second rotation
+        //third rotation
         piece.perform_counter_clockwise_rotation();
 
         let point = Point::new(1, 0);
@@ -590,23 +631,10 @@ mod tests {
         m.data[1][0] = true; m.data[1][1] = false;
         m.data[2][0] = true;  m.data[2][1] = false;
 
-        assert_eq!(Piece::new(PieceType::J,m,point),piece);
-
-        //third rotation
-        piece.perform_counter_clockwise_rotation();
-
-        let point = Point::new(1, 1);
-        let mut m = Matrix::new(2, 3);
-        m.data[0][0] = true; m.data[0][1] = false; m.data[0][2]=false;
-        m.data[1][0] = true; m.data[1][1] = true; m.data[1][2]=true;
-
-
-
-        assert_eq!(Piece::new(PieceType::J,m,point),piece);
+        assert_eq!(Piece::new(PieceType::J,m, point),piece);
 
         //back to start rotation
         piece.perform_counter_clockwise_rotation();
-
         assert_eq!(get_j_piece(),piece);
     }
 
@@ -617,25 +645,15 @@ mod tests {
         //first rotation
         piece.perform_counter_clockwise_rotation();
 
-        let point = Point::new(1, 1);
-        let mut m = Matrix::new(2, 3);
-        m.data[0][0] = false; m.data[0][1] = false; m.data[0][2]=true;
-        m.data[1][0] = true; m.data[1][1] = true; m.data[1][2]=true;
-
-        assert_eq!(Piece::new(PieceType::L,m,point),piece);
-
-        //second rotation
-        piece.perform_counter_clockwise_rotation();
-
         let point = Point::new(1, 1);
         let mut m = Matrix::new(3, 2);
         m.data[0][0] = true; m.data[0][1] = true;
         m.data[1][0] = false; m.data[1][1] = true;
         m.data[2][0] = false;  m.data[2][1] = true;
 
-        assert_eq!(Piece::new(PieceType::L,m,point),piece);
+        assert_eq!(Piece::new(PieceType::L,m, point),piece);
 
-        //third rotation
+        //second rotation
         piece.perform_counter_clockwise_rotation();
 
         let point = Point::new(0, 1);
@@ -643,12 +661,21 @@ mod tests {
         m.data[0][0] = true; m.data[0][1] = true; m.data[0][2]=true;
         m.data[1][0] = true; m.data[1][1] = false; m.data[1][2]=false;
 
-        assert_eq!(Piece::new(PieceType::L,m,point),piece);
-
-        //back to start rotation
+        assert_eq!(Piece::new(PieceType::L,m, point),piece);
 
+        //third rotation
         piece.perform_counter_clockwise_rotation();
 
+        let point = Point::new(1, 0);
+        let mut m = Matrix::new(3, 2);
+        m.data[0][0] = true; m.data[0][1] = false;
+        m.data[1][0] = true; m.data[1][1] = false;
+        m.data[2][0] = true;  m.data[2][1] = true;
+
+        assert_eq!(Piece::new(PieceType::L,m, point),piece);
+
+        //back to start rotation
+        piece.perform_counter_clockwise_rotation();
         assert_eq!(get_l_piece(),piece);
     }
 
@@ -744,37 +771,37 @@ mod tests {
         //first rotation
         piece.perform_counter_clockwise_rotation();
 
-        let point = Point::new(1, 0);
+        let point = Point::new(1, 1);
         let mut m = Matrix::new(3, 2);
-        m.data[0][0] = true; m.data[0][1] = false;
+        m.data[0][0] = false; m.data[0][1] = true;
         m.data[1][0] = true; m.data[1][1] = true;
-        m.data[2][0] = true; m.data[2][1] = false;
+        m.data[2][0] = false; m.data[2][1] = true;
+
+        assert_eq!(Piece::new(PieceType::T,m, point),piece);
 
-        assert_eq!(Piece::new(PieceType::T,m,point),piece);
         //second rotation
         piece.perform_counter_clockwise_rotation();
 
-        let point = Point::new(1, 1);
+        let point = Point::new(0, 1);
         let mut m = Matrix::new(2, 3);
-        m.data[0][0] = false; m.data[0][1] = true; m.data[0][2] = false;
-        m.data[1][0] = true; m.data[1][1] = true; m.data[1][2] = true;
+        m.data[0][0] = true; m.data[0][1] = true; m.data[0][2] = true;
+        m.data[1][0] = false; m.data[1][1] = true; m.data[1][2] = false;
 
-        assert_eq!(Piece::new(PieceType::T,m,point),piece);
+        assert_eq!(Piece::new(PieceType::T,m, point),piece);
 
         //third rotation
         piece.perform_counter_clockwise_rotation();
 
-        let point = Point::new(1, 1);
+        let point = Point::new(1, 0);
         let mut m = Matrix::new(3, 2);
-        m.data[0][0] = false; m.data[0][1] = true;
+        m.data[0][0] = true; m.data[0][1] = false;
         m.data[1][0] = true; m.data[1][1] = true;
-        m.data[2][0] = false; m.data[2][1] = true;
+        m.data[2][0] = true; m.data[2][1] = false;
 
-        assert_eq!(Piece::new(PieceType::T,m,point),piece);
+        assert_eq!(Piece::new(PieceType::T,m, point),piece);
 
         //back to start rotation
         piece.perform_counter_clockwise_rotation();
-
         assert_eq!(get_t_piece(),piece);
     }
 
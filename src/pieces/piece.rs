@@ -4,9 +4,9 @@ use utility::matrix::Matrix;
 use utility::point::Point;
 
 
-// Makes this struct cloneable and printable.
+// Makes this struct cloneable, printable and serializable.
 // Represents a piece by a piece_type, a boolean Matrix and the rotation point.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Piece {
     pub piece_type: PieceType,
     pub body: Matrix,
@@ -31,12 +31,32 @@ impl Piece {
     }
 
 
+    ///
+    /// This function returns the board coordinates of every tile of the piece, if it is added at the given row, column.
+    ///
+    /// The given row and column have to be at least as big as the rotation point.
+    ///
+    pub fn get_tiles(&self, row: usize, column: usize) -> Vec<Point> {
+        let mut tiles = vec![];
+
+        for x in 0..self.body.rows {
+            for y in 0..self.body.columns {
+                if self.body.data[x][y] {
+                    tiles.push(Point::new(row - self.point.x_coordinate + x, column - self.point.y_coordinate + y));
+                }
+            }
+        }
+
+        tiles
+    }
+
+
     ///
     /// This function performs the clockwise rotation.
     ///
     pub fn perform_clockwise_rotation(&mut self) {
-        let x_coordinate = self.point.x_coordinate.clone();
-        let y_coordinate = self.point.y_coordinate.clone();
+        let x_coordinate = self.point.x_coordinate;
+        let y_coordinate = self.point.y_coordinate;
 
         // Set the rotation point accordingly.
         self.point.set(y_coordinate, self.body.rows - 1 - x_coordinate);
@@ -49,8 +69,8 @@ impl Piece {
     /// This function performs the counter clockwise rotation.
     ///
     pub fn perform_counter_clockwise_rotation(&mut self) {
-        let x_coordinate = self.point.x_coordinate.clone();
-        let y_coordinate = self.point.y_coordinate.clone();
+        let x_coordinate = self.point.x_coordinate;
+        let y_coordinate = self.point.y_coordinate;
 
         // Set the rotation point accordingly.
         self.point.set(self.body.columns - 1 - y_coordinate, x_coordinate);
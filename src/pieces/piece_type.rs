@@ -1,6 +1,7 @@
-// Make this struct cloneable, printable and comparable.
+// Make this struct cloneable, printable, comparable and serializable.
 // Represents the different tetris piece_types.
-#[derive(Debug, Clone, PartialEq)]
+// Garbage is no piece, it marks the blocks of garbage rows pushed onto the board.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum PieceType {
     I,
     J,
@@ -9,5 +10,6 @@ pub enum PieceType {
     S,
     T,
     Z,
+    Garbage,
     None,
 }
\ No newline at end of file
@@ -0,0 +1,37 @@
+// Make this enum copyable, cloneable, printable, comparable, hashable and serializable.
+// Represents the four canonical rotation states of the Super Rotation System: 0, R, 2 and L.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum RotationState {
+    Zero,
+    Right,
+    Two,
+    Left,
+}
+
+
+impl RotationState {
+    ///
+    /// This function returns the state reached by a clockwise rotation.
+    ///
+    pub fn clockwise(&self) -> RotationState {
+        match self {
+            RotationState::Zero => RotationState::Right,
+            RotationState::Right => RotationState::Two,
+            RotationState::Two => RotationState::Left,
+            RotationState::Left => RotationState::Zero,
+        }
+    }
+
+
+    ///
+    /// This function returns the state reached by a counter clockwise rotation.
+    ///
+    pub fn counter_clockwise(&self) -> RotationState {
+        match self {
+            RotationState::Zero => RotationState::Left,
+            RotationState::Right => RotationState::Zero,
+            RotationState::Two => RotationState::Right,
+            RotationState::Left => RotationState::Two,
+        }
+    }
+}
@@ -0,0 +1,95 @@
+//!
+//! This module contains the data of the Super Rotation System (SRS).
+//!
+//! The kick offsets are written down as (x, y) pairs like in the guideline tables,
+//! where x points to the right and y points up. Keep that in mind when applying them to (row, column) coordinates.
+//!
+
+// Imports
+use pieces::piece_type::PieceType;
+use pieces::rotation_state::RotationState;
+
+
+// Kick offsets of the J, L, S, T and Z pieces.
+const JLSTZ_ZERO_TO_RIGHT: [(isize, isize); 5] = [(0, 0), (-1, 0), (-1, 1), (0, -2), (-1, -2)];
+const JLSTZ_RIGHT_TO_ZERO: [(isize, isize); 5] = [(0, 0), (1, 0), (1, -1), (0, 2), (1, 2)];
+const JLSTZ_RIGHT_TO_TWO: [(isize, isize); 5] = [(0, 0), (1, 0), (1, -1), (0, 2), (1, 2)];
+const JLSTZ_TWO_TO_RIGHT: [(isize, isize); 5] = [(0, 0), (-1, 0), (-1, 1), (0, -2), (-1, -2)];
+const JLSTZ_TWO_TO_LEFT: [(isize, isize); 5] = [(0, 0), (1, 0), (1, 1), (0, -2), (1, -2)];
+const JLSTZ_LEFT_TO_TWO: [(isize, isize); 5] = [(0, 0), (-1, 0), (-1, -1), (0, 2), (-1, 2)];
+const JLSTZ_LEFT_TO_ZERO: [(isize, isize); 5] = [(0, 0), (-1, 0), (-1, -1), (0, 2), (-1, 2)];
+const JLSTZ_ZERO_TO_LEFT: [(isize, isize); 5] = [(0, 0), (1, 0), (1, 1), (0, -2), (1, -2)];
+
+// Kick offsets of the I piece.
+const I_ZERO_TO_RIGHT: [(isize, isize); 5] = [(0, 0), (-2, 0), (1, 0), (-2, -1), (1, 2)];
+const I_RIGHT_TO_ZERO: [(isize, isize); 5] = [(0, 0), (2, 0), (-1, 0), (2, 1), (-1, -2)];
+const I_RIGHT_TO_TWO: [(isize, isize); 5] = [(0, 0), (-1, 0), (2, 0), (-1, 2), (2, -1)];
+const I_TWO_TO_RIGHT: [(isize, isize); 5] = [(0, 0), (1, 0), (-2, 0), (1, -2), (-2, 1)];
+const I_TWO_TO_LEFT: [(isize, isize); 5] = [(0, 0), (2, 0), (-1, 0), (2, 1), (-1, -2)];
+const I_LEFT_TO_TWO: [(isize, isize); 5] = [(0, 0), (-2, 0), (1, 0), (-2, -1), (1, 2)];
+const I_LEFT_TO_ZERO: [(isize, isize); 5] = [(0, 0), (1, 0), (-2, 0), (1, -2), (-2, 1)];
+const I_ZERO_TO_LEFT: [(isize, isize); 5] = [(0, 0), (-1, 0), (2, 0), (-1, 2), (2, -1)];
+
+// The O piece never kicks.
+const O_KICKS: [(isize, isize); 1] = [(0, 0)];
+
+
+///
+/// This function returns the kick offsets (x, y) that have to be tried in order,
+/// when the piece with the given piece_type rotates from the state "from" to the state "to".
+///
+pub fn get_kick_offsets(piece_type: &PieceType, from: RotationState, to: RotationState) -> Vec<(isize, isize)> {
+    match piece_type {
+        PieceType::O | PieceType::Garbage | PieceType::None => O_KICKS.to_vec(),
+        PieceType::I => match (from, to) {
+            (RotationState::Zero, RotationState::Right) => I_ZERO_TO_RIGHT.to_vec(),
+            (RotationState::Right, RotationState::Zero) => I_RIGHT_TO_ZERO.to_vec(),
+            (RotationState::Right, RotationState::Two) => I_RIGHT_TO_TWO.to_vec(),
+            (RotationState::Two, RotationState::Right) => I_TWO_TO_RIGHT.to_vec(),
+            (RotationState::Two, RotationState::Left) => I_TWO_TO_LEFT.to_vec(),
+            (RotationState::Left, RotationState::Two) => I_LEFT_TO_TWO.to_vec(),
+            (RotationState::Left, RotationState::Zero) => I_LEFT_TO_ZERO.to_vec(),
+            (RotationState::Zero, RotationState::Left) => I_ZERO_TO_LEFT.to_vec(),
+            _ => O_KICKS.to_vec(),
+        },
+        _ => match (from, to) {
+            (RotationState::Zero, RotationState::Right) => JLSTZ_ZERO_TO_RIGHT.to_vec(),
+            (RotationState::Right, RotationState::Zero) => JLSTZ_RIGHT_TO_ZERO.to_vec(),
+            (RotationState::Right, RotationState::Two) => JLSTZ_RIGHT_TO_TWO.to_vec(),
+            (RotationState::Two, RotationState::Right) => JLSTZ_TWO_TO_RIGHT.to_vec(),
+            (RotationState::Two, RotationState::Left) => JLSTZ_TWO_TO_LEFT.to_vec(),
+            (RotationState::Left, RotationState::Two) => JLSTZ_LEFT_TO_TWO.to_vec(),
+            (RotationState::Left, RotationState::Zero) => JLSTZ_LEFT_TO_ZERO.to_vec(),
+            (RotationState::Zero, RotationState::Left) => JLSTZ_ZERO_TO_LEFT.to_vec(),
+            _ => O_KICKS.to_vec(),
+        },
+    }
+}
+
+
+///
+/// This function returns the (row, column) position of the rotation point inside the SRS bounding box
+/// of the piece with the given piece_type in the given state.
+///
+/// The J, L, S, T and Z pieces rotate around their center tile, so the position never changes.
+/// The I and O pieces rotate around the center of their box instead, which lies between four tiles.
+/// Because a Piece always rotates around a tile, the board coordinates have to be corrected
+/// by the difference of these positions to get the true SRS rotation.
+///
+pub fn get_box_offset(piece_type: &PieceType, state: RotationState) -> (isize, isize) {
+    match piece_type {
+        PieceType::I => match state {
+            RotationState::Zero => (1, 1),
+            RotationState::Right => (1, 2),
+            RotationState::Two => (2, 2),
+            RotationState::Left => (2, 1),
+        },
+        PieceType::O => match state {
+            RotationState::Zero => (1, 1),
+            RotationState::Right => (1, 0),
+            RotationState::Two => (0, 0),
+            RotationState::Left => (0, 1),
+        },
+        _ => (1, 1),
+    }
+}
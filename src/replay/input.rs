@@ -0,0 +1,76 @@
+// Imports
+use game::Game;
+use game::move_direction::MoveDirection;
+use autoplayer::commands::Command;
+
+
+// Make this enum copyable, cloneable, printable, comparable and serializable.
+// Represents everything that can happen to a game, the player inputs as well as gravity and the lock delay.
+//
+// - Gravity:                   the piece falls one row, like Game::step(MoveDirection::Down)
+// - Left, Right:               the piece moves one column
+// - SoftDrop, HardDrop:        the player drops the piece
+// - RotateClockwise, ...:      the piece rotates
+// - Hold:                      the piece is held
+// - Lock:                      the lock delay ran out and the piece locked where it is
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Input {
+    Gravity,
+    Left,
+    Right,
+    SoftDrop,
+    HardDrop,
+    RotateClockwise,
+    RotateCounterClockwise,
+    Hold,
+    Lock,
+}
+
+
+impl Input {
+    ///
+    /// This function returns the input that corresponds to the given autoplayer command.
+    ///
+    pub fn from_command(command: &Command) -> Input {
+        match command {
+            Command::Down => Input::Gravity,
+            Command::Left => Input::Left,
+            Command::Right => Input::Right,
+            Command::RotateClockWise => Input::RotateClockwise,
+            Command::RotateCounterClockWise => Input::RotateCounterClockwise,
+            Command::Hold => Input::Hold,
+        }
+    }
+
+
+    ///
+    /// This function applies the input to the given game.
+    ///
+    /// It returns {true} if a new piece was added and {false} otherwise.
+    ///
+    pub fn apply(&self, game: &mut Game) -> bool {
+        match self {
+            Input::Gravity => game.step(MoveDirection::Down),
+            Input::Left => game.step(MoveDirection::Left),
+            Input::Right => game.step(MoveDirection::Right),
+            Input::SoftDrop => game.soft_drop(),
+            Input::HardDrop => game.hard_drop(),
+            Input::RotateClockwise => {
+                game.rotate_piece_clockwise();
+                false
+            }
+            Input::RotateCounterClockwise => {
+                game.rotate_piece_counter_clockwise();
+                false
+            }
+            Input::Hold => {
+                game.hold();
+                false
+            }
+            Input::Lock => {
+                game.lock_piece();
+                true
+            }
+        }
+    }
+}
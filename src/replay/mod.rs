@@ -0,0 +1,236 @@
+///
+/// This module records games and plays them back.
+///
+/// input:      everything that can happen to a game
+/// player:     the playback of a replay with pause, step, speed and seek
+///
+pub mod input;
+pub mod player;
+
+// Imports
+use std::env;
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde_json;
+
+use game::Game;
+use game::rules::Rules;
+use self::input::Input;
+
+
+// The version of the replay file format. Replays of other versions can't be loaded.
+pub const REPLAY_VERSION: u32 = 1;
+
+
+// Make this struct copyable, cloneable, printable, comparable and serializable.
+// Represents an input and the time in seconds since the start of the game it happened at.
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TimedInput {
+    pub time: f64,
+    pub input: Input,
+}
+
+
+// Make this struct cloneable, printable, comparable and serializable.
+// Represents a recorded game. Games with the same seed, rules and inputs always end up identical.
+//
+// - version:   the version of the file format
+// - seed:      the seed of the game
+// - rules:     the rules of the game
+// - autoplay:  indicates if the autoplayer played the game
+// - inputs:    every input in the order it happened
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Replay {
+    pub version: u32,
+    pub seed: u64,
+    pub rules: Rules,
+    pub autoplay: bool,
+    pub inputs: Vec<TimedInput>,
+}
+
+
+impl Replay {
+    // Start recording the given game, before anything happened to it.
+    pub fn new(game: &Game, autoplay: bool) -> Replay {
+        Replay {
+            version: REPLAY_VERSION,
+            seed: game.seed,
+            rules: Rules::from_game(game),
+            autoplay,
+            inputs: vec![],
+        }
+    }
+
+
+    ///
+    /// This function records an input that happened "time" seconds after the start of the game.
+    ///
+    pub fn record(&mut self, time: f64, input: Input) {
+        self.inputs.push(TimedInput { time, input });
+    }
+
+
+    ///
+    /// This function returns the time of the last input in seconds.
+    ///
+    pub fn duration(&self) -> f64 {
+        self.inputs.last().map_or(0.0, |timed_input| timed_input.time)
+    }
+
+
+    ///
+    /// This function creates the game as it was before the first input.
+    ///
+    /// Returns None if the rules contain an unknown piece generator.
+    ///
+    pub fn create_game(&self) -> Option<Game> {
+        self.rules.create_game(self.seed)
+    }
+
+
+    ///
+    /// This function re-simulates the whole game and returns it as it was after the last input.
+    ///
+    pub fn simulate(&self) -> Option<Game> {
+        let mut game = self.create_game()?;
+        for timed_input in &self.inputs {
+            timed_input.input.apply(&mut game);
+        }
+        Some(game)
+    }
+
+
+    ///
+    /// This function saves the replay to the given path, missing directories are created.
+    ///
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(directory) = path.parent() {
+            fs::create_dir_all(directory)?;
+        }
+        let writer = BufWriter::new(File::create(path)?);
+        serde_json::to_writer(writer, self).map_err(io::Error::from)
+    }
+
+
+    ///
+    /// This function loads the replay from the given path.
+    ///
+    /// Fails if the file is no replay, the version doesn't match or the piece generator is unknown.
+    ///
+    pub fn load(path: &Path) -> io::Result<Replay> {
+        let reader = BufReader::new(File::open(path)?);
+        let replay: Replay = serde_json::from_reader(reader).map_err(io::Error::from)?;
+
+        if replay.version != REPLAY_VERSION {
+            let error = format!("Replay version {} is not supported, expected version {}.", replay.version, REPLAY_VERSION);
+            return Err(io::Error::new(io::ErrorKind::InvalidData, error));
+        }
+        if replay.create_game().is_none() {
+            let error = format!("Unknown piece generator \"{}\".", replay.rules.generator);
+            return Err(io::Error::new(io::ErrorKind::InvalidData, error));
+        }
+
+        Ok(replay)
+    }
+
+
+    ///
+    /// This function returns a new path in the "replays" directory, named after the current time.
+    ///
+    pub fn default_path() -> PathBuf {
+        let seconds = SystemTime::now().duration_since(UNIX_EPOCH).map(|duration| duration.as_secs()).unwrap_or(0);
+        let directory = env::current_dir().unwrap_or_default().join("replays");
+        directory.join(format!("replay-{}.json", seconds))
+    }
+}
+
+
+///
+/// TESTS BEGIN HERE
+///
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::fs;
+
+    use board::Board;
+    use game::Game;
+    use game::lock_delay::{LockDelay, LockDelayMode};
+    use autoplayer::Autoplayer;
+    use replay::{Replay, REPLAY_VERSION};
+    use replay::input::Input;
+
+    // Let the autoplayer play and record every command like the GUI does.
+    fn record_autoplayer_game(seed: u64, pieces: usize) -> (Game, Replay) {
+        let mut game = Game::with_seed(Board::new(20, 10), seed);
+        game.lock_delay = LockDelay::new(LockDelayMode::Classic, 0.0);
+        let mut replay = Replay::new(&game, true);
+        let mut auto_player = Autoplayer::with_lookahead(0);
+
+        let mut time = 0.0;
+        while !game.is_game_over() && game.pieces_placed < pieces {
+            let command = auto_player.play_move(&mut game);
+            replay.record(time, Input::from_command(&command));
+            time += 0.01;
+        }
+        (game, replay)
+    }
+
+    #[test]
+    fn autoplayer_replay_is_deterministic() {
+        let (game, replay) = record_autoplayer_game(11, 60);
+        let simulated = replay.simulate().unwrap();
+
+        assert_eq!(simulated.board, game.board);
+        assert_eq!(simulated.points, game.points);
+        assert_eq!(simulated.rows, game.rows);
+        assert_eq!(simulated.pieces_placed, 60);
+    }
+
+    #[test]
+    fn lock_inputs_replace_the_lock_delay() {
+        let mut game = Game::with_seed(Board::new(20, 10), 5);
+        let mut replay = Replay::new(&game, false);
+        let mut inputs = vec![Input::Gravity, Input::Left, Input::SoftDrop, Input::RotateCounterClockwise, Input::Hold, Input::HardDrop, Input::Right];
+        inputs.extend(vec![Input::Gravity; 20]);
+
+        for (i, input) in inputs.iter().enumerate() {
+            input.apply(&mut game);
+            replay.record(i as f64, *input);
+        }
+
+        // The piece rests on the ground, so the guideline lock delay locks it once its time ran out.
+        assert!(game.update_lock_delay(1.0));
+        replay.record(30.0, Input::Lock);
+
+        let simulated = replay.simulate().unwrap();
+        assert_eq!(simulated.board, game.board);
+        assert_eq!(simulated.points, game.points);
+        assert_eq!(simulated.pieces_placed, 2);
+        assert_eq!(replay.duration(), 30.0);
+    }
+
+    #[test]
+    fn save_and_load() {
+        let (_, replay) = record_autoplayer_game(3, 10);
+        let path = env::temp_dir().join("tetris_replay_test").join("replay.json");
+
+        replay.save(&path).unwrap();
+        let loaded = Replay::load(&path).unwrap();
+        assert_eq!(loaded.seed, replay.seed);
+        assert_eq!(loaded.rules, replay.rules);
+        assert_eq!(loaded.inputs.len(), replay.inputs.len());
+        assert_eq!(loaded.simulate().unwrap().board, replay.simulate().unwrap().board);
+
+        // Other versions are rejected.
+        let mut newer = replay.clone();
+        newer.version = REPLAY_VERSION + 1;
+        newer.save(&path).unwrap();
+        assert!(Replay::load(&path).is_err());
+
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,199 @@
+// Imports
+use game::Game;
+use replay::Replay;
+
+
+// The playback speed can be halved and doubled between these values.
+const MIN_SPEED: f64 = 0.25;
+const MAX_SPEED: f64 = 16.0;
+
+
+///
+/// This struct represents the playback of a replay, independent of the frontend that shows it.
+///
+/// - replay:           the replay that is played
+/// - initial_game:     the game before the first input, used to seek backwards
+/// - game:             the game as it is at the current playback time
+/// - position:         the index of the next input that is applied
+/// - time:             the current playback time in seconds
+/// - paused:           indicates if the playback is paused
+/// - speed:            the playback speed, 1.0 is real time
+///
+pub struct ReplayPlayer {
+    pub replay: Replay,
+    pub initial_game: Game,
+    pub game: Game,
+    pub position: usize,
+    pub time: f64,
+    pub paused: bool,
+    pub speed: f64,
+}
+
+
+impl ReplayPlayer {
+    // Returns None if the replay contains an unknown piece generator.
+    pub fn new(replay: Replay) -> Option<ReplayPlayer> {
+        let initial_game = replay.create_game()?;
+        Some(ReplayPlayer {
+            replay,
+            game: initial_game.clone(),
+            initial_game,
+            position: 0,
+            time: 0.0,
+            paused: false,
+            speed: 1.0,
+        })
+    }
+
+
+    ///
+    /// This function lets "elapsed" seconds of real time pass and applies all inputs that are due.
+    ///
+    pub fn update(&mut self, elapsed: f64) {
+        if self.paused || self.is_finished() {
+            return;
+        }
+
+        self.time += elapsed * self.speed;
+        self.apply_until(self.time);
+    }
+
+
+    ///
+    /// This function pauses or resumes the playback.
+    ///
+    pub fn toggle_pause(&mut self) {
+        self.paused = !self.paused;
+    }
+
+
+    ///
+    /// This function pauses the playback and applies exactly the next input.
+    ///
+    pub fn step(&mut self) {
+        self.paused = true;
+        if let Some(timed_input) = self.replay.inputs.get(self.position) {
+            timed_input.input.apply(&mut self.game);
+            self.time = timed_input.time;
+            self.position += 1;
+        }
+    }
+
+
+    ///
+    /// This function doubles the playback speed.
+    ///
+    pub fn speed_up(&mut self) {
+        self.speed = (self.speed * 2.0).min(MAX_SPEED);
+    }
+
+
+    ///
+    /// This function halves the playback speed.
+    ///
+    pub fn slow_down(&mut self) {
+        self.speed = (self.speed / 2.0).max(MIN_SPEED);
+    }
+
+
+    ///
+    /// This function jumps to the given playback time in seconds.
+    ///
+    /// Seeking backwards re-simulates the game from the start, since the inputs can't be undone.
+    ///
+    pub fn seek(&mut self, time: f64) {
+        let time = time.max(0.0).min(self.replay.duration());
+
+        if time < self.time {
+            self.game = self.initial_game.clone();
+            self.position = 0;
+        }
+
+        self.time = time;
+        self.apply_until(time);
+    }
+
+
+    ///
+    /// This function checks if every input of the replay was applied.
+    ///
+    pub fn is_finished(&self) -> bool {
+        self.position >= self.replay.inputs.len()
+    }
+
+
+    ///
+    /// This function applies all inputs that happened until the given time.
+    ///
+    fn apply_until(&mut self, time: f64) {
+        while let Some(timed_input) = self.replay.inputs.get(self.position) {
+            if timed_input.time > time {
+                break;
+            }
+            timed_input.input.apply(&mut self.game);
+            self.position += 1;
+        }
+    }
+}
+
+
+///
+/// TESTS BEGIN HERE
+///
+#[cfg(test)]
+mod tests {
+    use board::Board;
+    use game::Game;
+    use replay::Replay;
+    use replay::input::Input;
+    use replay::player::ReplayPlayer;
+
+    fn replay() -> Replay {
+        let game = Game::with_seed(Board::new(20, 10), 9);
+        let mut replay = Replay::new(&game, false);
+        let inputs = [Input::Gravity, Input::Left, Input::HardDrop, Input::Right, Input::RotateClockwise, Input::HardDrop, Input::Hold, Input::HardDrop];
+        for (i, input) in inputs.iter().enumerate() {
+            replay.record(i as f64, *input);
+        }
+        replay
+    }
+
+    #[test]
+    fn update_applies_due_inputs_with_speed() {
+        let mut player = ReplayPlayer::new(replay()).unwrap();
+        player.update(0.5);
+        assert_eq!(player.position, 1);
+
+        player.speed_up();
+        player.update(1.0);
+        assert_eq!(player.position, 3);
+        assert_eq!(player.game.pieces_placed, 1);
+
+        player.toggle_pause();
+        player.update(10.0);
+        assert_eq!(player.position, 3);
+    }
+
+    #[test]
+    fn step_and_seek() {
+        let replay = replay();
+        let mut player = ReplayPlayer::new(replay.clone()).unwrap();
+        player.step();
+        player.step();
+        assert!(player.paused);
+        assert_eq!(player.position, 2);
+        assert_eq!(player.time, 1.0);
+
+        player.seek(100.0);
+        assert!(player.is_finished());
+        assert_eq!(player.game.board, replay.simulate().unwrap().board);
+
+        // Seeking backwards ends up in the same game as playing up to that point.
+        player.seek(2.5);
+        let mut other = ReplayPlayer::new(replay).unwrap();
+        other.update(2.5);
+        assert_eq!(player.position, 3);
+        assert_eq!(player.game.board, other.game.board);
+        assert_eq!(player.game.points, other.game.points);
+    }
+}
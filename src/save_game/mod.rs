@@ -0,0 +1,138 @@
+// Imports
+use std::env;
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter};
+use std::path::{Path, PathBuf};
+
+use serde_json;
+
+use game::Game;
+use replay::Replay;
+
+
+// The version of the save file format. Saves of other versions can't be loaded.
+pub const SAVE_GAME_VERSION: u32 = 1;
+
+
+// Make this struct cloneable, printable and serializable.
+// Represents an in-progress game, stored so that it can be continued later exactly where it was left.
+//
+// - version:   the version of the file format
+// - autoplay:  indicates if the autoplayer is playing the game
+// - game_time: the time in seconds the game was played so far
+// - game:      the whole game, including the board, the pieces, the score and the state of the rng
+// - replay:    the recording of the game so far, which is continued after loading
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SaveGame {
+    pub version: u32,
+    pub autoplay: bool,
+    pub game_time: f64,
+    pub game: Game,
+    pub replay: Replay,
+}
+
+
+impl SaveGame {
+    pub fn new(game: &Game, replay: &Replay, autoplay: bool, game_time: f64) -> SaveGame {
+        SaveGame {
+            version: SAVE_GAME_VERSION,
+            autoplay,
+            game_time,
+            game: game.clone(),
+            replay: replay.clone(),
+        }
+    }
+
+
+    ///
+    /// This function saves the game to the given path, missing directories are created.
+    ///
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(directory) = path.parent() {
+            fs::create_dir_all(directory)?;
+        }
+        let writer = BufWriter::new(File::create(path)?);
+        serde_json::to_writer(writer, self).map_err(io::Error::from)
+    }
+
+
+    ///
+    /// This function loads the game from the given path.
+    ///
+    /// Fails if the file is no saved game or the version doesn't match.
+    ///
+    pub fn load(path: &Path) -> io::Result<SaveGame> {
+        let reader = BufReader::new(File::open(path)?);
+        let save_game: SaveGame = serde_json::from_reader(reader).map_err(io::Error::from)?;
+
+        if save_game.version != SAVE_GAME_VERSION {
+            let error = format!("Save version {} is not supported, expected version {}.", save_game.version, SAVE_GAME_VERSION);
+            return Err(io::Error::new(io::ErrorKind::InvalidData, error));
+        }
+
+        Ok(save_game)
+    }
+
+
+    ///
+    /// This function returns the path of the one saved game, which is continued from the start screen.
+    ///
+    pub fn default_path() -> PathBuf {
+        env::current_dir().unwrap_or_default().join("saves").join("save_game.json")
+    }
+}
+
+
+///
+/// TESTS BEGIN HERE
+///
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::fs;
+
+    use board::Board;
+    use game::Game;
+    use pieces::generators::tgm_generator::TgmGenerator;
+    use replay::Replay;
+    use replay::input::Input;
+    use save_game::{SaveGame, SAVE_GAME_VERSION};
+
+    #[test]
+    fn loaded_game_continues_identically() {
+        let mut game = Game::with_generator(Board::new(20, 10), Box::new(TgmGenerator::new()), 17);
+        let mut replay = Replay::new(&game, false);
+        for input in [Input::Gravity, Input::Left, Input::Left, Input::HardDrop, Input::Hold, Input::RotateClockwise, Input::Gravity].iter() {
+            input.apply(&mut game);
+            replay.record(1.0, *input);
+        }
+
+        let path = env::temp_dir().join("tetris_save_game_test").join("save_game.json");
+        SaveGame::new(&game, &replay, false, 12.5).save(&path).unwrap();
+        let mut loaded = SaveGame::load(&path).unwrap();
+
+        assert_eq!(loaded.game.board, game.board);
+        assert_eq!(loaded.game.actual_piece, game.actual_piece);
+        assert_eq!(loaded.game.actual_piece_coordinates, game.actual_piece_coordinates);
+        assert_eq!(loaded.game.held_piece, game.held_piece);
+        assert_eq!(loaded.game_time, 12.5);
+        assert_eq!(loaded.replay, replay);
+
+        // The generator and the rng continue where they stopped, so both games get the same pieces.
+        for _ in 0..30 {
+            game.hard_drop();
+            loaded.game.hard_drop();
+            assert_eq!(loaded.game.next_piece, game.next_piece);
+        }
+        assert_eq!(loaded.game.board, game.board);
+        assert_eq!(loaded.game.points, game.points);
+
+        // Other versions are rejected.
+        let mut newer = SaveGame::new(&game, &replay, false, 0.0);
+        newer.version = SAVE_GAME_VERSION + 1;
+        newer.save(&path).unwrap();
+        assert!(SaveGame::load(&path).is_err());
+
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,181 @@
+// Make this enum cloneable, copyable, printable and comparable.
+// Represents everything a key can be bound to during a game.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Action {
+    MoveLeft,
+    MoveRight,
+    SoftDrop,
+    HardDrop,
+    RotateClockwise,
+    RotateCounterClockwise,
+    Hold,
+    Pause,
+    SaveAndQuit,
+}
+
+
+impl Action {
+    // All actions in the order they are shown on the settings screen.
+    pub const ALL: [Action; 9] = [
+        Action::MoveLeft,
+        Action::MoveRight,
+        Action::SoftDrop,
+        Action::HardDrop,
+        Action::RotateClockwise,
+        Action::RotateCounterClockwise,
+        Action::Hold,
+        Action::Pause,
+        Action::SaveAndQuit,
+    ];
+
+
+    ///
+    /// This function returns the name of the action shown on the settings screen.
+    ///
+    pub fn label(&self) -> &'static str {
+        match self {
+            Action::MoveLeft => "Move left",
+            Action::MoveRight => "Move right",
+            Action::SoftDrop => "Soft drop",
+            Action::HardDrop => "Hard drop",
+            Action::RotateClockwise => "Rotate clockwise",
+            Action::RotateCounterClockwise => "Rotate counter-clockwise",
+            Action::Hold => "Hold",
+            Action::Pause => "Pause",
+            Action::SaveAndQuit => "Save & quit",
+        }
+    }
+}
+
+
+// Make this struct cloneable, printable, comparable and serializable.
+// Represents the keys bound to every action, stored as the [controls] table of the settings file.
+// Keys are stored by their name, e.g. "Left", "Space" or "X", so that the engine doesn't depend on a frontend.
+// Every action can have several keys, but a key only belongs to one action.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Controls {
+    pub move_left: Vec<String>,
+    pub move_right: Vec<String>,
+    pub soft_drop: Vec<String>,
+    pub hard_drop: Vec<String>,
+    pub rotate_clockwise: Vec<String>,
+    pub rotate_counter_clockwise: Vec<String>,
+    pub hold: Vec<String>,
+    pub pause: Vec<String>,
+    pub save_and_quit: Vec<String>,
+}
+
+
+impl Default for Controls {
+    fn default() -> Controls {
+        Controls::new()
+    }
+}
+
+
+impl Controls {
+    // The controls the game always had.
+    pub fn new() -> Controls {
+        Controls {
+            move_left: keys(&["Left"]),
+            move_right: keys(&["Right"]),
+            soft_drop: keys(&["Down"]),
+            hard_drop: keys(&["Space"]),
+            rotate_clockwise: keys(&["Y"]),
+            rotate_counter_clockwise: keys(&["X"]),
+            hold: keys(&["C"]),
+            pause: keys(&["Escape", "P"]),
+            save_and_quit: keys(&["S"]),
+        }
+    }
+
+
+    ///
+    /// This function returns the keys bound to the given action.
+    ///
+    pub fn keys(&self, action: Action) -> &Vec<String> {
+        match action {
+            Action::MoveLeft => &self.move_left,
+            Action::MoveRight => &self.move_right,
+            Action::SoftDrop => &self.soft_drop,
+            Action::HardDrop => &self.hard_drop,
+            Action::RotateClockwise => &self.rotate_clockwise,
+            Action::RotateCounterClockwise => &self.rotate_counter_clockwise,
+            Action::Hold => &self.hold,
+            Action::Pause => &self.pause,
+            Action::SaveAndQuit => &self.save_and_quit,
+        }
+    }
+
+
+    fn keys_mut(&mut self, action: Action) -> &mut Vec<String> {
+        match action {
+            Action::MoveLeft => &mut self.move_left,
+            Action::MoveRight => &mut self.move_right,
+            Action::SoftDrop => &mut self.soft_drop,
+            Action::HardDrop => &mut self.hard_drop,
+            Action::RotateClockwise => &mut self.rotate_clockwise,
+            Action::RotateCounterClockwise => &mut self.rotate_counter_clockwise,
+            Action::Hold => &mut self.hold,
+            Action::Pause => &mut self.pause,
+            Action::SaveAndQuit => &mut self.save_and_quit,
+        }
+    }
+
+
+    ///
+    /// This function returns the action the key with the given name is bound to, if there is one.
+    ///
+    pub fn action(&self, key: &str) -> Option<Action> {
+        Action::ALL.iter().cloned().find(|action| self.keys(*action).iter().any(|bound| bound == key))
+    }
+
+
+    ///
+    /// This function binds the key to the action, replacing the keys the action had before.
+    ///
+    /// The key is taken away from any other action, so that every key stays unambiguous.
+    ///
+    pub fn bind(&mut self, action: Action, key: &str) {
+        for other in Action::ALL.iter() {
+            self.keys_mut(*other).retain(|bound| bound != key);
+        }
+        *self.keys_mut(action) = vec![key.to_string()];
+    }
+}
+
+
+///
+/// This function converts the key names to owned strings.
+///
+fn keys(names: &[&str]) -> Vec<String> {
+    names.iter().map(|name| name.to_string()).collect()
+}
+
+
+///
+/// TESTS BEGIN HERE
+///
+#[cfg(test)]
+mod tests {
+    use settings::controls::{Action, Controls};
+
+    #[test]
+    fn bind_keeps_keys_unambiguous() {
+        let mut controls = Controls::new();
+        assert_eq!(controls.action("Y"), Some(Action::RotateClockwise));
+        assert_eq!(controls.action("P"), Some(Action::Pause));
+        assert_eq!(controls.action("Up"), None);
+
+        // "X" moves from counter-clockwise to clockwise, counter-clockwise is left without a key.
+        controls.bind(Action::RotateClockwise, "X");
+        assert_eq!(controls.rotate_clockwise, vec!["X".to_string()]);
+        assert!(controls.rotate_counter_clockwise.is_empty());
+        assert_eq!(controls.action("X"), Some(Action::RotateClockwise));
+        assert_eq!(controls.action("Y"), None);
+
+        controls.bind(Action::RotateCounterClockwise, "Z");
+        assert_eq!(controls.action("Z"), Some(Action::RotateCounterClockwise));
+    }
+}
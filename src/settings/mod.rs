@@ -0,0 +1,247 @@
+///
+/// This module contains the settings, stored as a TOML file in the user's config directory.
+///
+pub mod controls;
+
+// Imports
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use dirs;
+use toml;
+
+use autoplayer::evaluator::{self, Evaluator, EvaluatorKind};
+use game::MAX_PREVIEW_SIZE;
+use game::auto_repeat::Handling;
+use settings::controls::Controls;
+use utility::gravity_curve::GravityCurve;
+
+
+// The limits of the settings, values outside of them are clamped when loading.
+pub const MIN_ROWS: usize = 10;
+pub const MAX_ROWS: usize = 30;
+pub const MIN_COLUMNS: usize = 4;
+pub const MAX_COLUMNS: usize = 20;
+pub const MAX_HIDDEN_ROWS: usize = 20;
+pub const MAX_STARTING_LEVEL: usize = 20;
+pub const MAX_DAS: f64 = 500.0;
+pub const MAX_ARR: f64 = 200.0;
+pub const MAX_SOFT_DROP_FACTOR: f64 = 40.0;
+
+
+// Make this struct cloneable, printable, comparable and serializable.
+// Represents the settings of the game. Missing values in the file get their default value.
+//
+// - sound:             indicates if any audio is played
+// - music_volume:      the volume of the theme song, from 0.0 to 1.0
+// - rows, columns:     the size of the visible board of new games
+// - hidden_rows:       the amount of buffer rows above the visible board of new games
+// - starting_level:    the level new games start at, level 0 is only allowed on the NES gravity curve
+// - gravity_curve:     the gravity curve of new games
+// - preview_size:      the amount of next pieces shown, from 0 to game::MAX_PREVIEW_SIZE
+// - evaluator:         the evaluation function of the autoplayer, with its preset weights
+// - weights_file:      a weights file for the autoplayer, it replaces "evaluator" if there is one
+// - handling:          the DAS, ARR and soft drop factor of held keys
+// - controls:          the keys bound to every action
+// The tables have to be the last fields, because TOML writes tables last.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub sound: bool,
+    pub music_volume: f32,
+    pub rows: usize,
+    pub columns: usize,
+    pub hidden_rows: usize,
+    pub starting_level: usize,
+    pub gravity_curve: GravityCurve,
+    pub preview_size: usize,
+    pub evaluator: EvaluatorKind,
+    pub weights_file: Option<String>,
+    pub handling: Handling,
+    pub controls: Controls,
+}
+
+
+impl Default for Settings {
+    fn default() -> Settings {
+        Settings::new()
+    }
+}
+
+
+impl Settings {
+    // The settings the game always had, on a board of the standard size.
+    pub fn new() -> Settings {
+        Settings {
+            sound: true,
+            music_volume: 1.0,
+            rows: 20,
+            columns: 10,
+            hidden_rows: 20,
+            starting_level: 1,
+            gravity_curve: GravityCurve::Guideline,
+            preview_size: 5,
+            evaluator: EvaluatorKind::Classic,
+            weights_file: None,
+            handling: Handling::new(),
+            controls: Controls::new(),
+        }
+    }
+
+
+    ///
+    /// This function clamps every value to its limits.
+    ///
+    pub fn clamp(&mut self) {
+        self.music_volume = self.music_volume.clamp(0.0, 1.0);
+        self.rows = self.rows.clamp(MIN_ROWS, MAX_ROWS);
+        self.columns = self.columns.clamp(MIN_COLUMNS, MAX_COLUMNS);
+        self.hidden_rows = self.hidden_rows.min(MAX_HIDDEN_ROWS);
+        self.starting_level = self.starting_level.clamp(self.gravity_curve.first_level(), MAX_STARTING_LEVEL);
+        self.preview_size = self.preview_size.min(MAX_PREVIEW_SIZE);
+        self.handling.das = self.handling.das.clamp(0.0, MAX_DAS);
+        self.handling.arr = self.handling.arr.clamp(0.0, MAX_ARR);
+        self.handling.soft_drop_factor = self.handling.soft_drop_factor.clamp(1.0, MAX_SOFT_DROP_FACTOR);
+    }
+
+
+    ///
+    /// This function creates the evaluator of the autoplayer.
+    ///
+    /// If the weights file can't be loaded, the error is printed and the preset weights of "evaluator" are used.
+    ///
+    pub fn evaluator(&self) -> Box<dyn Evaluator> {
+        if let Some(ref weights_file) = self.weights_file {
+            match evaluator::load_evaluator(Path::new(weights_file)) {
+                Ok(evaluator) => return evaluator,
+                Err(e) => println!("Weights Error, {} in /settings/mod.rs", e),
+            }
+        }
+
+        self.evaluator.evaluator()
+    }
+
+
+    ///
+    /// This function saves the settings to the given path, missing directories are created.
+    ///
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(directory) = path.parent() {
+            fs::create_dir_all(directory)?;
+        }
+        let text = toml::to_string_pretty(self).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        fs::write(path, text)
+    }
+
+
+    ///
+    /// This function loads the settings from the given path.
+    ///
+    /// If there is no file yet, the default settings are returned. Fails if the file is no valid TOML.
+    ///
+    pub fn load(path: &Path) -> io::Result<Settings> {
+        if !path.exists() {
+            return Ok(Settings::new());
+        }
+
+        let text = fs::read_to_string(path)?;
+        let mut settings: Settings = toml::from_str(&text).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        settings.clamp();
+
+        Ok(settings)
+    }
+
+
+    ///
+    /// This function loads the settings from the default path, errors are printed and the default settings are used.
+    ///
+    pub fn load_or_default() -> Settings {
+        match Settings::load(&Settings::default_path()) {
+            Ok(settings) => settings,
+            Err(e) => {
+                println!("Settings Error, {} in /settings/mod.rs", e);
+                Settings::new()
+            }
+        }
+    }
+
+
+    ///
+    /// This function returns the path of the settings file in the user's config directory.
+    ///
+    /// The current directory is used, if the system doesn't have a config directory.
+    ///
+    pub fn default_path() -> PathBuf {
+        let directory = dirs::config_dir().unwrap_or_else(|| env::current_dir().unwrap_or_default());
+        directory.join("tetris").join("settings.toml")
+    }
+}
+
+
+///
+/// TESTS BEGIN HERE
+///
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::fs;
+
+    use toml;
+
+    use autoplayer::evaluator::EvaluatorKind;
+    use settings::Settings;
+    use settings::controls::Action;
+    use utility::gravity_curve::GravityCurve;
+
+    #[test]
+    fn missing_values_get_defaults() {
+        let settings: Settings = toml::from_str("rows = 22\n\n[controls]\nhold = [\"Tab\"]\n").unwrap();
+
+        assert_eq!(settings.rows, 22);
+        assert_eq!(settings.columns, Settings::new().columns);
+        assert_eq!(settings.controls.action("Tab"), Some(Action::Hold));
+        assert_eq!(settings.controls.action("Left"), Some(Action::MoveLeft));
+    }
+
+    #[test]
+    fn save_and_load() {
+        let path = env::temp_dir().join("tetris_settings_test").join("settings.toml");
+        let _ = fs::remove_file(&path);
+        assert_eq!(Settings::load(&path).unwrap(), Settings::new());
+
+        let mut settings = Settings::new();
+        settings.sound = false;
+        settings.music_volume = 0.5;
+        settings.starting_level = 5;
+        settings.gravity_curve = GravityCurve::Tgm20G;
+        settings.controls.bind(Action::RotateCounterClockwise, "Z");
+        settings.handling.arr = 0.0;
+        settings.evaluator = EvaluatorKind::ElTetris;
+        settings.weights_file = Some("weights.toml".to_string());
+        settings.save(&path).unwrap();
+        assert_eq!(Settings::load(&path).unwrap(), settings);
+
+        // Values out of their limits are clamped.
+        fs::write(&path, "rows = 100\nmusic_volume = 2.0\nstarting_level = 0\npreview_size = 9\n\n[handling]\ndas = 1000.0\n").unwrap();
+        let loaded = Settings::load(&path).unwrap();
+        assert_eq!(loaded.rows, 30);
+        assert_eq!(loaded.handling.das, 500.0);
+        assert_eq!(loaded.handling.arr, Settings::new().handling.arr);
+        assert_eq!(loaded.music_volume, 1.0);
+        assert_eq!(loaded.starting_level, 1);
+        assert_eq!(loaded.preview_size, 6);
+
+        // Only NES Tetris has a level 0.
+        fs::write(&path, "starting_level = 0\ngravity_curve = \"Nes\"\n").unwrap();
+        let loaded = Settings::load(&path).unwrap();
+        assert_eq!(loaded.gravity_curve, GravityCurve::Nes);
+        assert_eq!(loaded.starting_level, 0);
+
+        fs::write(&path, "rows = \"many\"").unwrap();
+        assert!(Settings::load(&path).is_err());
+
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,136 @@
+// Imports.
+use game::Game;
+use game::lock_delay::LockDelay;
+use autoplayer::Autoplayer;
+
+
+///
+/// This struct represents the outcome of a single headless autoplayer game.
+///
+/// - seed:          the seed the game was played with
+/// - rows:          the number of completed rows
+/// - points:        the points achieved
+/// - pieces_placed: the number of pieces that were locked onto the board
+///
+// Make this struct cloneable, printable and comparable.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SimulationResult {
+    pub seed: u64,
+    pub rows: usize,
+    pub points: usize,
+    pub pieces_placed: usize,
+}
+
+
+///
+/// This struct represents the aggregate of several values, e.g. the points of all simulated games.
+///
+// Make this struct cloneable, printable and comparable.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Statistics {
+    pub mean: f64,
+    pub median: f64,
+    pub max: usize,
+}
+
+
+impl Statistics {
+    ///
+    /// This function computes the mean, median and maximum of the given values.
+    ///
+    /// All of them are 0 if there are no values.
+    ///
+    pub fn new(values: &[usize]) -> Statistics {
+        if values.is_empty() {
+            return Statistics { mean: 0.0, median: 0.0, max: 0 };
+        }
+
+        let mut sorted = values.to_vec();
+        sorted.sort_unstable();
+
+        let middle = sorted.len() / 2;
+        let median = if sorted.len() % 2 == 1 {
+            sorted[middle] as f64
+        } else {
+            (sorted[middle - 1] + sorted[middle]) as f64 / 2.0
+        };
+
+        Statistics {
+            mean: sorted.iter().sum::<usize>() as f64 / sorted.len() as f64,
+            median,
+            max: sorted[sorted.len() - 1],
+        }
+    }
+}
+
+
+///
+/// This function lets the autoplayer play a whole game without any rendering or real-time gravity.
+///
+/// The given game, usually a new one created with a seed, is played by the given autoplayer.
+/// It ends when it is over or when "piece_limit" pieces have been placed, if a limit is given.
+///
+pub fn simulate_game(mut game: Game, mut auto_player: Autoplayer, piece_limit: Option<usize>) -> SimulationResult {
+    game.lock_delay = LockDelay::autoplayer();
+
+    while !game.is_game_over() {
+        if let Some(limit) = piece_limit {
+            if game.pieces_placed >= limit {
+                break;
+            }
+        }
+        auto_player.play_move(&mut game);
+    }
+
+    SimulationResult {
+        seed: game.seed,
+        rows: game.rows,
+        points: game.points,
+        pieces_placed: game.pieces_placed,
+    }
+}
+
+
+///
+/// TESTS BEGIN HERE
+///
+#[cfg(test)]
+mod tests {
+    use autoplayer::Autoplayer;
+    use board::Board;
+    use game::Game;
+    use simulator::{simulate_game, Statistics};
+
+    #[test]
+    fn statistics_of_odd_and_even_counts() {
+        let odd = Statistics::new(&[3, 1, 2]);
+        assert_eq!(odd, Statistics { mean: 2.0, median: 2.0, max: 3 });
+
+        let even = Statistics::new(&[4, 1, 3, 10]);
+        assert_eq!(even, Statistics { mean: 4.5, median: 3.5, max: 10 });
+
+        assert_eq!(Statistics::new(&[]), Statistics { mean: 0.0, median: 0.0, max: 0 });
+    }
+
+    #[test]
+    fn simulation_respects_the_piece_limit() {
+        let result = simulate_game(Game::with_seed(Board::new(20, 10), 7), Autoplayer::with_lookahead(0), Some(25));
+        assert_eq!(result.pieces_placed, 25);
+        assert_eq!(result.seed, 7);
+
+        let hidden = simulate_game(Game::with_seed(Board::with_hidden_rows(20, 10, 4), 7), Autoplayer::with_lookahead(0), Some(25));
+        assert_eq!(hidden.pieces_placed, 25);
+    }
+
+    #[test]
+    fn simulation_is_deterministic() {
+        let play = || simulate_game(Game::with_seed(Board::new(20, 10), 42), Autoplayer::with_lookahead(0), Some(50));
+        assert_eq!(play(), play());
+    }
+
+    #[test]
+    fn simulation_with_lookahead() {
+        let result = simulate_game(Game::with_seed(Board::new(20, 10), 3), Autoplayer::with_lookahead(1), Some(10));
+        assert_eq!(result.pieces_placed, 10);
+    }
+}
@@ -0,0 +1,195 @@
+// Imports.
+use std::io::{self, Write};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use termion::{clear, cursor};
+use termion::event::Key;
+
+use tui::TUI;
+use game::Game;
+use game::lock_delay::LockDelay;
+use autoplayer::{self, Autoplayer};
+use autoplayer::evaluator::Evaluator;
+use board::Board;
+use replay::Replay;
+use replay::input::Input;
+use utility;
+
+
+// The terminal is redrawn roughly 60 times per second.
+const FRAME_DURATION_MS: u64 = 16;
+
+
+///
+/// This struct represents the Main_state of the terminal frontend, where the game itself will happen.
+///
+/// - tui:                  the information of the TUI
+/// - game:                 the game
+/// - time_since_step:      used to compute the automatic move-down correctly
+/// - autoplay:             indicates if the autoplayer is running
+/// - quit:                 indicates if the player quit the game early
+/// - auto_player:          the actual auto_player
+/// - replay:               the recording of every input applied to the game
+/// - game_time:            the time in seconds since the game started, used to timestamp the inputs
+///
+pub struct MainState {
+    pub tui: TUI,
+    pub game: Game,
+    pub time_since_step: f64,
+    pub autoplay: bool,
+    pub quit: bool,
+    pub auto_player: Autoplayer,
+    pub replay: Replay,
+    pub game_time: f64,
+}
+
+
+impl MainState {
+    ///
+    /// This function creates the main state for a new game on the given empty board, the TUI gets its size.
+    /// The autoplayer uses the given evaluator.
+    ///
+    pub fn new(autoplay: bool, board: Board, evaluator: Box<dyn Evaluator>) -> MainState {
+        let tui = TUI::with_board_size(board.visible_rows(), board.columns);
+        let mut game = Game::new(board);
+
+        if autoplay {
+            game.lock_delay = LockDelay::autoplayer();
+        }
+        let auto_player = Autoplayer::with_evaluator(evaluator, autoplayer::DEFAULT_LOOKAHEAD);
+        let replay = Replay::new(&game, autoplay);
+        MainState {
+            tui,
+            game,
+            time_since_step: 0.0,
+            autoplay,
+            quit: false,
+            auto_player,
+            replay,
+            game_time: 0.0,
+        }
+    }
+
+
+    ///
+    /// This function runs the game until it is over or the player quits.
+    ///
+    /// Every frame all pending keys are handled, the game logic is updated and the game is drawn into "out".
+    /// "keys" has to be non-blocking, e.g. termion::async_stdin().keys(), and "out" should be in raw mode.
+    ///
+    pub fn run<I, W>(&mut self, keys: &mut I, out: &mut W) -> io::Result<()>
+        where I: Iterator<Item = io::Result<Key>>, W: Write {
+        write!(out, "{}{}", clear::All, cursor::Hide)?;
+        self.tui.draw_controls(out, self.autoplay)?;
+
+        let mut last_update = Instant::now();
+        while !self.quit && !self.game.is_game_over() {
+            for key in keys.by_ref() {
+                self.key_down_event(key?);
+            }
+
+            let now = Instant::now();
+            let elapsed = now.duration_since(last_update);
+            last_update = now;
+
+            self.update(elapsed.as_secs_f64());
+            self.tui.draw_content(&self.game, out)?;
+
+            thread::sleep(Duration::from_millis(FRAME_DURATION_MS));
+        }
+
+        write!(out, "{}", cursor::Show)?;
+        out.flush()
+    }
+
+
+    ///
+    /// Here is where the game logic takes place. "elapsed" is the time in seconds since the last update.
+    ///
+    pub fn update(&mut self, elapsed: f64) {
+        let duration = utility::duration_for_level(&self.game);
+        self.time_since_step += elapsed;
+        self.game_time += elapsed;
+
+        // Autoplayer is active.
+        if self.autoplay {
+            if self.time_since_step >= duration {
+                if self.apply(Input::Gravity) {
+                    self.auto_player.compute_move(&mut self.game);
+                }
+                self.time_since_step = 0.0;
+            } else {
+                let command = self.auto_player.play_move(&mut self.game);
+                self.replay.record(self.game_time, Input::from_command(&command));
+            }
+            // Human player is active.
+        } else {
+            if self.time_since_step >= duration {
+                // Fast gravity curves move the piece down several rows per update.
+                let rows = (self.time_since_step / duration) as usize;
+                for _ in 0..rows.min(self.game.board.rows) {
+                    if self.apply(Input::Gravity) {
+                        break;
+                    }
+                }
+                self.time_since_step = 0.0;
+            }
+
+            // Lock the piece once it rested on the ground for long enough.
+            // The replay doesn't know about the time, so it records the moment the piece locked.
+            if self.game.update_lock_delay(elapsed) {
+                self.replay.record(self.game_time, Input::Lock);
+            }
+        }
+    }
+
+
+    ///
+    /// This function records the input in the replay and applies it to the game.
+    ///
+    /// It returns {true} if a new piece was added and {false} otherwise.
+    ///
+    pub fn apply(&mut self, input: Input) -> bool {
+        self.replay.record(self.game_time, input);
+        input.apply(&mut self.game)
+    }
+
+
+    ///
+    /// This function handles key_events.
+    /// Controls are:   Left, Right, Down   - to move the piece in the given direction.
+    ///                 Space               - to hard drop the piece.
+    ///                 c                   - to hold the piece.
+    ///                 y and x             - to rotate the piece clock- and counter- clockwise.
+    ///                 q and Escape        - to quit the game early.
+    ///
+    pub fn key_down_event(&mut self, key: Key) {
+        if self.game.is_game_over() {
+            return;
+        }
+
+        // Quitting is allowed for both the human and the autoplayer.
+        if key == Key::Char('q') || key == Key::Esc || key == Key::Ctrl('c') {
+            self.quit = true;
+            return;
+        }
+
+        if self.autoplay {
+            return;
+        }
+
+        // Match on the key that was pressed.
+        let input = match key {
+            Key::Left => Input::Left,
+            Key::Right => Input::Right,
+            Key::Down => Input::SoftDrop,
+            Key::Char(' ') => Input::HardDrop,
+            Key::Char('c') => Input::Hold,
+            Key::Char('y') => Input::RotateClockwise,
+            Key::Char('x') => Input::RotateCounterClockwise,
+            _ => return,
+        };
+        self.apply(input);
+    }
+}
@@ -0,0 +1,234 @@
+///
+/// This module represents the terminal frontend of the Tetris game, for machines where no window can be opened:
+/// TUI:                draws the game with ANSI colours into the terminal
+/// Main_state:         representing the game itself, reads the keyboard in raw mode
+///
+pub mod main_state;
+
+// Imports
+use std::io::{self, Write};
+
+use termion::{clear, cursor, style};
+use termion::color::{self, AnsiValue, Bg, Fg};
+
+use game::Game;
+use game::top_out::TopOut;
+use pieces::piece::Piece;
+use pieces::piece_type::PieceType;
+
+
+pub struct TUI {
+    pub columns: usize,
+    pub rows: usize,
+    pub board_left: u16,
+    pub board_top: u16,
+    pub side_panel_left: u16,
+}
+
+
+impl Default for TUI {
+    fn default() -> TUI {
+        TUI::new()
+    }
+}
+
+
+impl TUI {
+    // DEFAULT PARAMETERS FOR THE TUI
+    pub fn new() -> TUI {
+        TUI::with_board_size(20, 10)
+    }
+
+
+    ///
+    /// This function creates the TUI for a board with the given amount of visible rows and columns.
+    ///
+    /// Every tile is two characters wide, so the side panel starts right of the board and its border.
+    ///
+    pub fn with_board_size(rows: usize, columns: usize) -> TUI {
+        let board_left = 2;
+        let board_top = 1;
+        let side_panel_left = board_left + 2 * columns as u16 + 4;
+
+        TUI {
+            columns,
+            rows,
+            board_left,
+            board_top,
+            side_panel_left,
+        }
+    }
+
+
+    ///
+    /// This function is used to draw the game contents into the terminal.
+    ///
+    /// Everything is written into one buffer first, so that a frame appears at once and doesn't flicker:
+    ///     (1) Draw the board with its border, the ghost piece and all the pieces on it.
+    ///     (2) Draw the next_piece and the held_piece.
+    ///     (3) Draw the standings (completed rows, level and achieved points).
+    ///     (4) Write the buffer into the terminal and flush it.
+    ///
+    pub fn draw_content<W: Write>(&self, game: &Game, out: &mut W) -> io::Result<()> {
+        let mut frame = String::new();
+
+        self.draw_board(&mut frame, game);
+        self.draw_piece_box(&mut frame, &game.next_piece, self.board_top, "Next:");
+        self.draw_piece_box(&mut frame, &game.held_piece, self.board_top + 6, "Hold:");
+        self.draw_standings(&mut frame, game);
+
+        write!(out, "{}{}", frame, cursor::Goto(1, self.board_top + game.board.visible_rows() as u16 + 3))?;
+        out.flush()
+    }
+
+
+    ///
+    /// This function draws the board, its border and all the tiles.
+    ///
+    /// The ghost piece, where the actual piece would land, is drawn as brackets in the colour of the actual piece.
+    /// Only the visible rows are drawn, the hidden rows above them stay off the screen.
+    ///
+    fn draw_board(&self, frame: &mut String, game: &Game) {
+        let board = &game.board;
+        let hidden_rows = board.hidden_rows;
+
+        let mut ghost_tiles = vec![];
+        if game.actual_piece.piece_type != PieceType::None {
+            let ghost = game.ghost_position();
+            ghost_tiles = game.actual_piece.get_tiles(ghost.x_coordinate, ghost.y_coordinate);
+        }
+
+        let horizontal_border = "-".repeat(2 * board.columns);
+        frame.push_str(&format!("{}+{}+", cursor::Goto(self.board_left, self.board_top), horizontal_border));
+
+        for x in hidden_rows..board.rows {
+            frame.push_str(&format!("{}|", cursor::Goto(self.board_left, self.board_top + 1 + (x - hidden_rows) as u16)));
+            for y in 0..board.columns {
+                if board.board[x][y] != PieceType::None {
+                    frame.push_str(&self.tile(&board.board[x][y]));
+                } else if ghost_tiles.iter().any(|tile| tile.x_coordinate == x && tile.y_coordinate == y) {
+                    let colour = self.get_colour_for_piece_type(&game.actual_piece.piece_type);
+                    frame.push_str(&format!("{}[]{}", Fg(colour), Fg(color::Reset)));
+                } else {
+                    frame.push_str(&format!("{} .{}", Fg(AnsiValue::grayscale(8)), Fg(color::Reset)));
+                }
+            }
+            frame.push('|');
+        }
+
+        frame.push_str(&format!("{}+{}+", cursor::Goto(self.board_left, self.board_top + 1 + board.visible_rows() as u16), horizontal_border));
+    }
+
+
+    ///
+    /// This function draws the given piece with the given label into the side panel, starting at the given line.
+    ///
+    fn draw_piece_box(&self, frame: &mut String, piece: &Piece, top: u16, label: &str) {
+        frame.push_str(&format!("{}{}{}", cursor::Goto(self.side_panel_left, top), label, clear::UntilNewline));
+
+        // Clear the four lines below the label, the previous piece might have been larger.
+        for i in 0..4 {
+            frame.push_str(&format!("{}{}", cursor::Goto(self.side_panel_left, top + 1 + i), clear::UntilNewline));
+        }
+
+        // If the piece_type is NONE don't draw anything.
+        if piece.piece_type == PieceType::None {
+            return;
+        }
+
+        for i in 0..piece.body.rows {
+            frame.push_str(&format!("{}", cursor::Goto(self.side_panel_left, top + 1 + i as u16)));
+            for j in 0..piece.body.columns {
+                if piece.body.data[i][j] {
+                    frame.push_str(&self.tile(&piece.piece_type));
+                } else {
+                    frame.push_str("  ");
+                }
+            }
+        }
+    }
+
+
+    ///
+    /// This function draws the standings completed_rows / level / achieved_points into the side panel.
+    /// Below them the name of the last clear like "T-Spin Double" is shown.
+    ///
+    fn draw_standings(&self, frame: &mut String, game: &Game) {
+        let top = self.board_top + 12;
+        let clear_text = match game.last_clear_event {
+            Some(ref clear_event) => clear_event.name(),
+            None => String::new(),
+        };
+
+        let lines = [
+            format!("Rows:   {}", game.rows),
+            format!("Level:  {}", game.level),
+            format!("Points: {}", game.points),
+            String::new(),
+            clear_text,
+        ];
+
+        for (i, line) in lines.iter().enumerate() {
+            frame.push_str(&format!("{}{}{}", cursor::Goto(self.side_panel_left, top + i as u16), line, clear::UntilNewline));
+        }
+    }
+
+
+    ///
+    /// This function draws the controls below the standings.
+    ///
+    pub fn draw_controls<W: Write>(&self, out: &mut W, autoplay: bool) -> io::Result<()> {
+        let top = self.board_top + 18;
+        let lines: &[&str] = if autoplay {
+            &["Autoplayer is playing.", "q / Esc  quit"]
+        } else {
+            &["Left / Right  move", "Down          soft drop", "Space         hard drop", "c             hold", "y / x         rotate", "q / Esc       quit"]
+        };
+
+        for (i, line) in lines.iter().enumerate() {
+            write!(out, "{}{}", cursor::Goto(self.side_panel_left, top + i as u16), line)?;
+        }
+
+        out.flush()
+    }
+
+
+    ///
+    /// This function draws the game over screen with the achieved points, the completed rows and the reason the game ended.
+    ///
+    pub fn draw_game_over<W: Write>(&self, out: &mut W, game_rows: usize, game_points: usize, top_out: Option<TopOut>) -> io::Result<()> {
+        write!(out, "{}{}", clear::All, cursor::Goto(1, 1))?;
+        write!(out, "{}GAME OVER{}\r\n", style::Bold, style::Reset)?;
+        write!(out, "{}\r\n\r\n", top_out.map(|top_out| top_out.name()).unwrap_or(""))?;
+        write!(out, "Rows:   {}\r\nPoints: {}\r\n\r\n", game_rows, game_points)?;
+        write!(out, "Press r to play again or any other key to quit.\r\n")?;
+
+        out.flush()
+    }
+
+
+    ///
+    /// This function returns a tile of two characters in the colour of the given piece_type.
+    ///
+    fn tile(&self, piece_type: &PieceType) -> String {
+        format!("{}  {}", Bg(self.get_colour_for_piece_type(piece_type)), Bg(color::Reset))
+    }
+
+
+    ///
+    /// This function returns an ANSI colour (of the 256 colour palette) for the given piece_type.
+    ///
+    fn get_colour_for_piece_type(&self, piece_type: &PieceType) -> AnsiValue {
+        match piece_type {
+            PieceType::I => AnsiValue(51),                  // lightblue
+            PieceType::J => AnsiValue(21),                  // blue
+            PieceType::L => AnsiValue(208),                 // orange
+            PieceType::O => AnsiValue(226),                 // yellow
+            PieceType::S => AnsiValue(118),                 // green
+            PieceType::T => AnsiValue(129),                 // purple
+            PieceType::Z => AnsiValue(196),                 // red
+            PieceType::Garbage => AnsiValue(242),           // grey
+            PieceType::None => AnsiValue(231),              // white
+        }
+    }
+}
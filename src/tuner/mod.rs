@@ -0,0 +1,373 @@
+// Imports
+use std::cmp::Ordering;
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter};
+use std::path::Path;
+use std::sync::atomic::{self, AtomicUsize};
+use std::thread;
+
+use rand::SeedableRng;
+use rand::distributions::{Distribution, Normal};
+use rand_pcg::Pcg32;
+use serde_json;
+
+use autoplayer::Autoplayer;
+use autoplayer::evaluator::{Evaluator, EvaluatorKind, WeightsFile};
+use board::Board;
+use game::Game;
+use simulator;
+use settings::Settings;
+
+
+// The version of the checkpoint file format. Checkpoints of other versions can't be loaded.
+pub const CHECKPOINT_VERSION: u32 = 1;
+
+
+// Make this struct cloneable, printable, comparable and serializable.
+// Represents the options of the tuner, they are stored in the checkpoint and can't change after the tuning started.
+//
+// - population:        the amount of weight vectors sampled every generation
+// - elite:             the amount of the best weight vectors the next distribution is fitted to
+// - games:             the amount of games every weight vector plays, all weight vectors of a generation play the same games
+// - seed:              the seed of the first game, every generation plays games with seeds of its own
+// - rows, columns:     the size of the visible board of the games
+// - hidden_rows:       the amount of hidden rows above the visible board, checkpoints without them were tuned without hidden rows
+// - piece_limit:       the maximum amount of pieces of a game
+// - preview:           the amount of preview pieces of the games
+// - lookahead:         the amount of preview pieces the autoplayer takes into account
+// - noise:             the variance added to every weight after fitting, so that the distribution doesn't collapse too early
+// - noise_generations: the amount of generations the added noise decreases to 0 in
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TunerConfig {
+    pub population: usize,
+    pub elite: usize,
+    pub games: usize,
+    pub seed: u64,
+    pub rows: usize,
+    pub columns: usize,
+    #[serde(default)]
+    pub hidden_rows: usize,
+    pub piece_limit: usize,
+    pub preview: usize,
+    pub lookahead: usize,
+    pub noise: f64,
+    pub noise_generations: usize,
+}
+
+
+impl Default for TunerConfig {
+    fn default() -> TunerConfig {
+        TunerConfig::new()
+    }
+}
+
+
+impl TunerConfig {
+    pub fn new() -> TunerConfig {
+        TunerConfig {
+            population: 50,
+            elite: 10,
+            games: 5,
+            seed: 0,
+            rows: 20,
+            columns: 10,
+            hidden_rows: Settings::new().hidden_rows,
+            piece_limit: 1000,
+            preview: 1,
+            lookahead: 0,
+            noise: 1.0,
+            noise_generations: 20,
+        }
+    }
+}
+
+
+// Make this struct cloneable, printable, comparable and serializable.
+// Represents a weight vector together with the mean amount of rows it completed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Candidate {
+    pub weights: Vec<f64>,
+    pub score: f64,
+}
+
+
+///
+/// This struct represents the state of the noisy cross-entropy method, it's stored as the checkpoint.
+///
+/// Every generation samples weight vectors from a normal distribution per weight, lets them play and fits the distributions to the best ones.
+///
+/// - version:      the version of the file format
+/// - config:       the options of the tuner
+/// - evaluator:    the kind of the evaluator whose weights are tuned
+/// - names:        the names of the tuned weights
+/// - mean:         the mean of every weight's distribution
+/// - deviation:    the standard deviation of every weight's distribution
+/// - generation:   the amount of generations played so far
+/// - best:         the weight vector with the best score so far
+/// - rng:          the state of the rng the weight vectors are sampled with
+///
+// Make this struct cloneable, printable and serializable.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Tuner {
+    pub version: u32,
+    pub config: TunerConfig,
+    pub evaluator: EvaluatorKind,
+    pub names: Vec<String>,
+    pub mean: Vec<f64>,
+    pub deviation: Vec<f64>,
+    pub generation: usize,
+    pub best: Option<Candidate>,
+    pub rng: Pcg32,
+}
+
+
+impl Tuner {
+    ///
+    /// This function creates a tuner for the weights of the given evaluator, the distributions start at its weights.
+    ///
+    /// The weight vectors are sampled with an rng seeded with the seed of the config.
+    ///
+    pub fn new(evaluator: &dyn Evaluator, config: TunerConfig, deviation: f64) -> Tuner {
+        let weights = evaluator.weights();
+        Tuner {
+            version: CHECKPOINT_VERSION,
+            evaluator: evaluator.kind(),
+            names: weights.iter().map(|(name, _)| name.to_string()).collect(),
+            mean: weights.iter().map(|(_, weight)| *weight).collect(),
+            deviation: vec![deviation; weights.len()],
+            generation: 0,
+            best: None,
+            rng: Pcg32::seed_from_u64(config.seed),
+            config,
+        }
+    }
+
+
+    ///
+    /// This function creates the evaluator with the given weights.
+    ///
+    pub fn evaluator(&self, weights: &[f64]) -> Box<dyn Evaluator> {
+        let mut evaluator = self.evaluator.evaluator();
+        for (name, weight) in self.names.iter().zip(weights.iter()) {
+            evaluator.set_weight(name, *weight);
+        }
+
+        evaluator
+    }
+
+
+    ///
+    /// This function returns the weights file of the best weight vector so far, if there is one.
+    ///
+    pub fn weights_file(&self) -> Option<WeightsFile> {
+        self.best.as_ref().map(|best| WeightsFile::new(&*self.evaluator(&best.weights)))
+    }
+
+
+    ///
+    /// This function plays one generation on the given amount of threads and returns its best weight vector.
+    ///
+    pub fn run_generation(&mut self, threads: usize) -> Candidate {
+        let population = self.sample();
+        let scores = self.score(&population, threads);
+        self.update(population, scores)
+    }
+
+
+    ///
+    /// This function samples the weight vectors of the next generation.
+    ///
+    pub fn sample(&mut self) -> Vec<Vec<f64>> {
+        let distributions: Vec<Normal> = self.mean.iter().zip(self.deviation.iter())
+            .map(|(mean, deviation)| Normal::new(*mean, *deviation))
+            .collect();
+
+        let rng = &mut self.rng;
+        (0..self.config.population)
+            .map(|_| distributions.iter().map(|distribution| distribution.sample(rng)).collect())
+            .collect()
+    }
+
+
+    ///
+    /// This function returns the mean amount of completed rows of every weight vector.
+    ///
+    /// The games are distributed over the given amount of threads, the scores don't depend on it.
+    ///
+    pub fn score(&self, population: &[Vec<f64>], threads: usize) -> Vec<f64> {
+        let games = self.config.games.max(1);
+        let jobs = population.len() * games;
+        let next_job = AtomicUsize::new(0);
+        let mut rows = vec![0; jobs];
+
+        thread::scope(|scope| {
+            let workers: Vec<_> = (0..threads.max(1)).map(|_| scope.spawn(|| {
+                let mut results = vec![];
+                loop {
+                    let job = next_job.fetch_add(1, atomic::Ordering::SeqCst);
+                    if job >= jobs {
+                        break;
+                    }
+                    results.push((job, self.play(&population[job / games], job % games)));
+                }
+                results
+            })).collect();
+
+            for worker in workers {
+                for (job, completed_rows) in worker.join().expect("A tuner thread panicked.") {
+                    rows[job] = completed_rows;
+                }
+            }
+        });
+
+        rows.chunks(games).map(|chunk| chunk.iter().sum::<usize>() as f64 / games as f64).collect()
+    }
+
+
+    ///
+    /// This function plays the given game of the actual generation with the given weights and returns the completed rows.
+    ///
+    fn play(&self, weights: &[f64], game: usize) -> usize {
+        let seed = self.config.seed.wrapping_add((self.generation * self.config.games + game) as u64);
+        let mut new_game = Game::with_seed(Board::with_hidden_rows(self.config.rows, self.config.columns, self.config.hidden_rows), seed);
+        new_game.set_preview_size(self.config.preview);
+
+        let auto_player = Autoplayer::with_evaluator(self.evaluator(weights), self.config.lookahead);
+        simulator::simulate_game(new_game, auto_player, Some(self.config.piece_limit)).rows
+    }
+
+
+    ///
+    /// This function fits the distributions to the elite of the scored population and returns the best weight vector of it.
+    ///
+    /// The variance of every weight gets the noise of the actual generation added, it decreases linearly to 0 over "noise_generations".
+    ///
+    pub fn update(&mut self, population: Vec<Vec<f64>>, scores: Vec<f64>) -> Candidate {
+        let mut candidates: Vec<Candidate> = population.into_iter().zip(scores)
+            .map(|(weights, score)| Candidate { weights, score })
+            .collect();
+        candidates.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(Ordering::Equal));
+
+        let elite = &candidates[..self.config.elite.clamp(1, candidates.len())];
+        let remaining = self.config.noise_generations.saturating_sub(self.generation) as f64;
+        let noise = self.config.noise * remaining / self.config.noise_generations.max(1) as f64;
+
+        for weight in 0..self.mean.len() {
+            let mean = elite.iter().map(|candidate| candidate.weights[weight]).sum::<f64>() / elite.len() as f64;
+            let variance = elite.iter().map(|candidate| (candidate.weights[weight] - mean).powi(2)).sum::<f64>() / elite.len() as f64;
+            self.mean[weight] = mean;
+            self.deviation[weight] = (variance + noise).sqrt();
+        }
+
+        let generation_best = candidates[0].clone();
+        if self.best.as_ref().is_none_or(|best| generation_best.score > best.score) {
+            self.best = Some(generation_best.clone());
+        }
+        self.generation += 1;
+
+        generation_best
+    }
+
+
+    ///
+    /// This function saves the checkpoint to the given path, missing directories are created.
+    ///
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(directory) = path.parent() {
+            fs::create_dir_all(directory)?;
+        }
+        let writer = BufWriter::new(File::create(path)?);
+        serde_json::to_writer_pretty(writer, self).map_err(io::Error::from)
+    }
+
+
+    ///
+    /// This function loads the checkpoint from the given path.
+    ///
+    /// Fails if the file is no checkpoint or the version doesn't match.
+    ///
+    pub fn load(path: &Path) -> io::Result<Tuner> {
+        let reader = BufReader::new(File::open(path)?);
+        let tuner: Tuner = serde_json::from_reader(reader).map_err(io::Error::from)?;
+
+        if tuner.version != CHECKPOINT_VERSION {
+            let error = format!("Checkpoint version {} is not supported, expected version {}.", tuner.version, CHECKPOINT_VERSION);
+            return Err(io::Error::new(io::ErrorKind::InvalidData, error));
+        }
+
+        Ok(tuner)
+    }
+}
+
+
+///
+/// TESTS BEGIN HERE
+///
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::fs;
+
+    use autoplayer::evaluator::EvaluatorKind;
+    use tuner::{Tuner, TunerConfig};
+
+    fn small_config() -> TunerConfig {
+        let mut config = TunerConfig::new();
+        config.population = 4;
+        config.elite = 2;
+        config.games = 2;
+        config.piece_limit = 15;
+        config.seed = 3;
+        config
+    }
+
+    #[test]
+    fn update_fits_the_elite() {
+        let mut config = small_config();
+        config.noise = 0.0;
+        let mut tuner = Tuner::new(&*EvaluatorKind::Classic.evaluator(), config, 1.0);
+
+        let population = vec![vec![1.0; 5], vec![2.0; 5], vec![3.0; 5], vec![5.0; 5]];
+        let best = tuner.update(population, vec![10.0, 3.0, 20.0, 1.0]);
+
+        assert_eq!(best.score, 20.0);
+        assert_eq!(tuner.mean, vec![2.0; 5]);
+        assert_eq!(tuner.deviation, vec![1.0; 5]);
+        assert_eq!(tuner.generation, 1);
+
+        // A worse generation doesn't replace the best weights.
+        tuner.update(vec![vec![0.0; 5]; 4], vec![0.0; 4]);
+        assert_eq!(tuner.best.unwrap().score, 20.0);
+    }
+
+    #[test]
+    fn scores_do_not_depend_on_the_threads() {
+        let mut tuner = Tuner::new(&*EvaluatorKind::ElTetris.evaluator(), small_config(), 1.0);
+        let population = tuner.sample();
+        assert_eq!(population.len(), 4);
+        assert_eq!(tuner.score(&population, 1), tuner.score(&population, 3));
+    }
+
+    #[test]
+    fn resume_from_checkpoint() {
+        let path = env::temp_dir().join("tetris_tuner_test").join("checkpoint.json");
+
+        let mut tuner = Tuner::new(&*EvaluatorKind::Dellacherie.evaluator(), small_config(), 0.5);
+        tuner.run_generation(2);
+        tuner.save(&path).unwrap();
+
+        // The loaded tuner continues like the one that kept running, JSON only keeps the weights up to rounding.
+        let mut resumed = Tuner::load(&path).unwrap();
+        let close = |a: &[f64], b: &[f64]| a.iter().zip(b.iter()).all(|(a, b)| (a - b).abs() < 1e-9);
+        let (kept, continued) = (tuner.run_generation(2), resumed.run_generation(2));
+        assert!(close(&kept.weights, &continued.weights));
+        assert!(close(&tuner.mean, &resumed.mean));
+        assert_eq!(resumed.generation, 2);
+
+        let weights_file = resumed.weights_file().unwrap();
+        assert_eq!(weights_file.evaluator, EvaluatorKind::Dellacherie);
+        assert!(weights_file.evaluator().is_ok());
+
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,69 @@
+// The frame rate of the NTSC version of NES Tetris.
+const NES_FRAMES_PER_SECOND: f64 = 60.0988;
+
+// The frame rate of TGM, where 20G means 20 rows per frame.
+const TGM_FRAMES_PER_SECOND: f64 = 60.0;
+
+
+// Make this enum copyable, cloneable, printable, comparable and serializable.
+// Represents the different gravity curves, that decide how fast the pieces fall on each level.
+//
+// - Guideline:    the formula of the Tetris guideline, (0.8 - (level - 1) * 0.007)^(level - 1) seconds per row
+// - Nes:          the frames per row table of the original NES Tetris
+// - Tgm20G:       the 20G gravity of Tetris The Grand Master, the pieces fall to the bottom instantly on every level
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub enum GravityCurve {
+    Guideline,
+    Nes,
+    Tgm20G,
+}
+
+
+impl GravityCurve {
+    pub const ALL: [GravityCurve; 3] = [GravityCurve::Guideline, GravityCurve::Nes, GravityCurve::Tgm20G];
+
+
+    ///
+    /// This function returns the lowest level of the gravity curve, NES Tetris starts at level 0, the others at level 1.
+    ///
+    pub fn first_level(&self) -> usize {
+        match self {
+            GravityCurve::Nes => 0,
+            GravityCurve::Guideline | GravityCurve::Tgm20G => 1,
+        }
+    }
+
+
+    ///
+    /// This function returns the time in seconds it takes a piece to fall one row on the given level.
+    ///
+    pub fn seconds_per_row(&self, level: usize) -> f64 {
+        match self {
+            GravityCurve::Guideline => {
+                let level = level.clamp(1, 20) as f64;
+                (0.8 - (level - 1.0) * 0.007).powf(level - 1.0)
+            },
+            GravityCurve::Nes => {
+                let frames = match level {
+                    0 => 48.0,
+                    1 => 43.0,
+                    2 => 38.0,
+                    3 => 33.0,
+                    4 => 28.0,
+                    5 => 23.0,
+                    6 => 18.0,
+                    7 => 13.0,
+                    8 => 8.0,
+                    9 => 6.0,
+                    10..=12 => 5.0,
+                    13..=15 => 4.0,
+                    16..=18 => 3.0,
+                    19..=28 => 2.0,
+                    _ => 1.0,
+                };
+                frames / NES_FRAMES_PER_SECOND
+            },
+            GravityCurve::Tgm20G => 1.0 / (20.0 * TGM_FRAMES_PER_SECOND),
+        }
+    }
+}
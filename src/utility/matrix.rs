@@ -1,6 +1,6 @@
-// Make this struct cloneable. printable and comparable.
+// Make this struct cloneable. printable, comparable and serializable.
 // Represents a boolean Matrix as a Vec<Vec<bool>> with rows, columns.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Matrix {
     pub rows: usize,
     pub columns: usize,
@@ -31,7 +31,7 @@ impl Matrix {
         // Rotate the entries.
         for x in 0..rows {
             for y in 0..columns {
-                new_matrix.data[y][rows - 1 - x] = self.data[x][y].clone();
+                new_matrix.data[y][rows - 1 - x] = self.data[x][y];
             }
         }
 
@@ -54,7 +54,7 @@ impl Matrix {
         // Rotate the entries of the matrix.
         for x in 0..rows {
             for y in 0..columns {
-                new_matrix.data[columns - 1 - y][x] = self.data[x][y].clone();
+                new_matrix.data[columns - 1 - y][x] = self.data[x][y];
             }
         }
 
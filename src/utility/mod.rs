@@ -1,7 +1,11 @@
+// The module banners below aren't the doc comments of the items after them.
+#![allow(clippy::empty_line_after_doc_comments)]
+
 pub mod matrix;
 pub mod point;
+pub mod gravity_curve;
 
-use Game;
+use game::Game;
 
 ///
 /// This module represents a utility module. Functions that are used throughout the game but don't belong anywhere else.
@@ -9,40 +13,10 @@ use Game;
 
 ///
 /// This function returns the time as f64 that it takes until the next automatic move_down of the tetris game occurs.
-/// Values of the original NES Tetris were used here.
+/// The time depends on the level and the gravity_curve of the game.
 ///
 pub fn duration_for_level(game: &Game) -> f64 {
-    if game.rows < 10 {
-        0.8
-    } else if game.rows < 20 {
-        0.72
-    } else if game.rows < 30 {
-        0.63
-    } else if game.rows < 40 {
-        0.55
-    } else if game.rows < 50 {
-        0.47
-    } else if game.rows < 60 {
-        0.38
-    } else if game.rows < 70 {
-        0.3
-    } else if game.rows < 80 {
-        0.22
-    } else if game.rows < 90 {
-        0.13
-    } else if game.rows < 100 {
-        0.1
-    } else if game.rows < 130 {
-        0.08
-    } else if game.rows < 160 {
-        0.07
-    } else if game.rows < 190 {
-        0.05
-    } else if game.rows < 290 {
-        0.03
-    } else {
-        0.02
-    }
+    game.gravity_curve.seconds_per_row(game.level)
 }
 
 
@@ -50,9 +24,11 @@ pub fn duration_for_level(game: &Game) -> f64 {
 /// TESTS FOR THE UTILITY MODULES.
 ///
 #[cfg(test)]
+#[allow(unused_mut, clippy::bool_assert_comparison)]
 mod tests {
     use utility::point::Point;
     use utility::matrix::Matrix;
+    use utility::gravity_curve::GravityCurve;
 
     #[test]
     fn point_creation_set() {
@@ -94,4 +70,27 @@ mod tests {
         assert_eq!(m.columns,5);
     }
 
-}
\ No newline at end of file
+
+    #[test]
+    fn guideline_gravity() {
+        assert_eq!(GravityCurve::Guideline.seconds_per_row(1), 1.0);
+        assert!((GravityCurve::Guideline.seconds_per_row(2) - 0.793).abs() < 1e-9);
+        for level in 1..20 {
+            assert!(GravityCurve::Guideline.seconds_per_row(level + 1) < GravityCurve::Guideline.seconds_per_row(level));
+        }
+    }
+
+    #[test]
+    fn nes_gravity() {
+        assert!((GravityCurve::Nes.seconds_per_row(0) - 48.0 / 60.0988).abs() < 1e-9);
+        assert_eq!(GravityCurve::Nes.seconds_per_row(10), GravityCurve::Nes.seconds_per_row(12));
+        assert!((GravityCurve::Nes.seconds_per_row(29) - 1.0 / 60.0988).abs() < 1e-9);
+    }
+
+    #[test]
+    fn tgm_gravity() {
+        // 20 rows per frame on every level.
+        assert!((GravityCurve::Tgm20G.seconds_per_row(1) * 20.0 * 60.0 - 1.0).abs() < 1e-9);
+        assert_eq!(GravityCurve::Tgm20G.seconds_per_row(1), GravityCurve::Tgm20G.seconds_per_row(500));
+    }
+}
@@ -1,6 +1,6 @@
-// Make this struct copyable, cloneable. printable and comparable.
+// Make this struct copyable, cloneable. printable, comparable and serializable.
 // Represents a simple 2d point with usize type values.
-#[derive(Debug, Copy, Clone, PartialEq)]
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Point {
     pub x_coordinate: usize,
     pub y_coordinate: usize,